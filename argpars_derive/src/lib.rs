@@ -0,0 +1,475 @@
+//! # argpars_derive
+//!
+//! Dependency-less derive macro for the argpars Command Line Argument Parser.
+//! Use it through the `derive` feature of argpars.
+//!
+//! # Usage
+//!
+//! ```no_run
+//! use argpars::*;
+//!
+//! #[derive(Argpars)]
+//! struct Cli {
+//!     /// explain what is being done
+//!     #[argpars(short = "-v")]
+//!     verbose: bool,
+//!     /// port to listen on
+//!     #[argpars(default = "8080")]
+//!     port: u16,
+//!     /// files to process
+//!     file: Vec<String>,
+//!     /// input file
+//!     #[argpars(positional, required)]
+//!     input: String,
+//! }
+//!
+//! let cli: Cli = Cli::parse();
+//! ```
+//!
+//! # LICENSE
+//!
+//! This project is distributed under MIT license.
+
+use proc_macro::{Delimiter, TokenStream, TokenTree};
+
+/// Field of the derived struct
+struct Field {
+    name: String,
+    ty: String,
+    description: String,
+    short: Option<String>,
+    default: Option<String>,
+    required: bool,
+    positional: bool,
+    hidden: bool,
+    group: Option<String>,
+    negatable: bool,
+    delimiter: Option<String>,
+    value_name: Option<String>,
+}
+
+/// Derive macro generating an `argpars::FromArgs` implementation for a struct with named fields
+///
+/// Each field becomes an argument named after it (`dry_run` becomes `--dry-run`), with its doc comment
+/// used as the description. `bool` fields are flags, `Vec<T>` (or `Option<Vec<T>>`) fields accept multiple
+/// parameters, `Option<T>` fields are optional and other fields are parsed with `FromStr`, being required
+/// unless they have a default.
+/// Fields can be configured with `#[argpars(short = "-v", default = "8080", required, positional)]`,
+/// as well as `group = "..."`, `delimiter = ","`, `value_name = "PATH"`, `hidden` and `negatable`
+///
+/// # Examples
+///
+/// ```
+/// use argpars::*;
+///
+/// #[derive(Argpars)]
+/// struct Cli {
+///     /// explain what is being done
+///     #[argpars(short = "-v")]
+///     verbose: bool,
+///     /// port to listen on
+///     #[argpars(default = "8080")]
+///     port: u16,
+///     /// files to process
+///     file: Option<Vec<String>>,
+///     /// input file
+///     #[argpars(positional)]
+///     input: String,
+/// }
+///
+/// let cli: Cli = Cli::try_parse_from(split("app in.txt -v --file a.txt b.txt")).unwrap();
+/// assert!(cli.verbose);
+/// assert_eq!(cli.port, 8080);
+/// assert_eq!(cli.file, Some(vec!["a.txt".to_string(), "b.txt".to_string()]));
+/// assert_eq!(cli.input, "in.txt");
+///
+/// let cli: Cli = Cli::try_parse_from(split("app in.txt --port 80")).unwrap();
+/// assert!(!cli.verbose);
+/// assert_eq!(cli.port, 80);
+/// assert_eq!(cli.file, None);
+///
+/// assert_eq!(
+///     Cli::try_parse_from(split("app --port 80")).err(),
+///     Some(ArgparsError::MissingRequiredPositional("INPUT".to_string()))
+/// );
+/// assert_eq!(
+///     Cli::try_parse_from(split("app in.txt --port many")).err(),
+///     Some(ArgparsError::InvalidValue("--port".to_string(), "many".to_string()))
+/// );
+/// ```
+///
+/// Fields without a default are required:
+///
+/// ```
+/// use argpars::*;
+///
+/// #[derive(Argpars)]
+/// struct Cli {
+///     /// number of jobs
+///     jobs: u32,
+/// }
+///
+/// assert_eq!(Cli::try_parse_from(split("app --jobs 4")).unwrap().jobs, 4);
+/// assert_eq!(
+///     Cli::try_parse_from(split("app")).err(),
+///     Some(ArgparsError::MissingRequiredArgument("--jobs".to_string()))
+/// );
+/// ```
+///
+/// Types which can't be parsed from arguments are rejected:
+///
+/// ```compile_fail
+/// use argpars::*;
+///
+/// #[derive(Argpars)]
+/// struct Cli {
+///     /// input files
+///     #[argpars(positional)]
+///     input: Vec<String>,
+/// }
+/// ```
+#[proc_macro_derive(Argpars, attributes(argpars))]
+pub fn derive_argpars(input: TokenStream) -> TokenStream {
+    match expand(input) {
+        Ok(output) => output.parse().unwrap(),
+        Err(message) => format!("compile_error!({:?});", message).parse().unwrap(),
+    }
+}
+
+// Returns the generated implementation, or a message describing why the input is not supported
+fn expand(input: TokenStream) -> Result<String, String> {
+    let mut tokens = input.into_iter().peekable();
+    let mut name: Option<String> = None;
+    let mut body: Option<TokenStream> = None;
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Ident(ident) if ident.to_string() == "struct" => {
+                if let Some(TokenTree::Ident(ident)) = tokens.next() {
+                    name = Some(ident.to_string());
+                }
+                match tokens.next() {
+                    Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace => {
+                        body = Some(group.stream());
+                    }
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == '<' => {
+                        return Err("#[derive(Argpars)] does not support generic structs".into());
+                    }
+                    _ => {
+                        return Err("#[derive(Argpars)] requires a struct with named fields".into())
+                    }
+                }
+                break;
+            }
+            TokenTree::Ident(ident)
+                if ident.to_string() == "enum" || ident.to_string() == "union" =>
+            {
+                return Err("#[derive(Argpars)] can only be used on structs".into());
+            }
+            _ => {}
+        }
+    }
+    let name: String = name.ok_or("#[derive(Argpars)] can only be used on structs")?;
+    let body: TokenStream = body.ok_or("#[derive(Argpars)] requires a struct with named fields")?;
+    let fields: Vec<Field> = parse_fields(body)?;
+    for field in &fields {
+        check_type(field)?;
+    }
+
+    let mut register: String = String::new();
+    let mut build: String = String::new();
+    for field in &fields {
+        register.push_str(&register_field(field));
+        build.push_str(&format!("{}: {},\n", field.name, build_field(field)));
+    }
+
+    Ok(format!(
+        "impl ::argpars::FromArgs for {name} {{
+            fn register(args: &mut ::argpars::ArgsObj) {{
+                use ::argpars::Argpars;
+                {register}
+            }}
+            fn from_parsed(args: &::argpars::ArgsObj) -> ::std::result::Result<Self, ::argpars::ArgparsError> {{
+                use ::argpars::Argpars;
+                ::std::result::Result::Ok({name} {{
+                    {build}
+                }})
+            }}
+        }}"
+    ))
+}
+
+// Returns fields of a struct body, with their attributes
+fn parse_fields(body: TokenStream) -> Result<Vec<Field>, String> {
+    let mut fields: Vec<Field> = Vec::new();
+    let mut tokens = body.into_iter().peekable();
+    while tokens.peek().is_some() {
+        let mut field: Field = Field {
+            name: String::new(),
+            ty: String::new(),
+            description: String::new(),
+            short: None,
+            default: None,
+            required: false,
+            positional: false,
+            hidden: false,
+            group: None,
+            negatable: false,
+            delimiter: None,
+            value_name: None,
+        };
+        // Attributes and visibility
+        loop {
+            match tokens.peek() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == '#' => {
+                    tokens.next();
+                    if let Some(TokenTree::Group(group)) = tokens.next() {
+                        parse_attribute(group.stream(), &mut field)?;
+                    }
+                }
+                Some(TokenTree::Ident(ident)) if ident.to_string() == "pub" => {
+                    tokens.next();
+                    if let Some(TokenTree::Group(group)) = tokens.peek() {
+                        if group.delimiter() == Delimiter::Parenthesis {
+                            tokens.next();
+                        }
+                    }
+                }
+                _ => break,
+            }
+        }
+        match tokens.next() {
+            Some(TokenTree::Ident(ident)) => field.name = ident.to_string(),
+            None => break,
+            _ => return Err("#[derive(Argpars)] requires a struct with named fields".into()),
+        }
+        match tokens.next() {
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ':' => {}
+            _ => return Err("#[derive(Argpars)] requires a struct with named fields".into()),
+        }
+        // Type, up to the next comma outside of angle brackets
+        let mut depth: i32 = 0;
+        let mut previous_is_word: bool = false;
+        for token in tokens.by_ref() {
+            let is_word: bool = match &token {
+                TokenTree::Punct(punct) => {
+                    match punct.as_char() {
+                        '<' => depth += 1,
+                        '>' => depth -= 1,
+                        ',' if depth == 0 => break,
+                        _ => {}
+                    }
+                    false
+                }
+                TokenTree::Ident(_) | TokenTree::Literal(_) => true,
+                TokenTree::Group(_) => false,
+            };
+            if is_word && previous_is_word {
+                field.ty.push(' ');
+            }
+            field.ty.push_str(&token.to_string());
+            previous_is_word = is_word;
+        }
+        fields.push(field);
+    }
+    Ok(fields)
+}
+
+// Reads a doc comment or an `argpars(...)` attribute into a field
+fn parse_attribute(attribute: TokenStream, field: &mut Field) -> Result<(), String> {
+    let tokens: Vec<TokenTree> = attribute.into_iter().collect();
+    match tokens.first().map(|t| t.to_string()).as_deref() {
+        Some("doc") => {
+            if let Some(TokenTree::Literal(literal)) = tokens.get(2) {
+                let line: String = unquote(&literal.to_string());
+                if !field.description.is_empty() {
+                    field.description.push(' ');
+                }
+                field.description.push_str(line.trim());
+            }
+        }
+        Some("argpars") => {
+            let options: Vec<TokenTree> = match tokens.get(1) {
+                Some(TokenTree::Group(group)) => group.stream().into_iter().collect(),
+                _ => return Err("expected #[argpars(...)]".into()),
+            };
+            for option in options.split(|t| matches!(t, TokenTree::Punct(p) if p.as_char() == ','))
+            {
+                let key: String = match option.first() {
+                    Some(token) => token.to_string(),
+                    None => continue,
+                };
+                let value: Option<String> = match option.get(2) {
+                    Some(TokenTree::Literal(literal)) => Some(unquote(&literal.to_string())),
+                    _ => None,
+                };
+                match (key.as_str(), value) {
+                    ("short", Some(value)) => field.short = Some(value),
+                    ("default", Some(value)) => field.default = Some(value),
+                    ("group", Some(value)) => field.group = Some(value),
+                    ("value_name", Some(value)) => field.value_name = Some(value),
+                    ("delimiter", Some(value)) if value.chars().count() == 1 => {
+                        field.delimiter = Some(value)
+                    }
+                    ("required", None) => field.required = true,
+                    ("positional", None) => field.positional = true,
+                    ("hidden", None) => field.hidden = true,
+                    ("negatable", None) => field.negatable = true,
+                    _ => return Err(format!("unknown argpars option: {}", key)),
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+// Returns the content of a string literal
+fn unquote(literal: &str) -> String {
+    let content: &str = literal
+        .strip_prefix('"')
+        .and_then(|l| l.strip_suffix('"'))
+        .unwrap_or(literal);
+    content.replace("\\\"", "\"").replace("\\\\", "\\")
+}
+
+// Returns the inner type of a generic wrapper (e.g. `T` for `Vec<T>`), if the type is that wrapper
+fn inner_type<'a>(ty: &'a str, wrapper: &str) -> Option<&'a str> {
+    ty.strip_prefix(wrapper)
+        .and_then(|t| t.strip_prefix('<'))
+        .and_then(|t| t.strip_suffix('>'))
+}
+
+// Returns the type of values of a field taking multiple parameters (`T` for `Vec<T>` or `Option<Vec<T>>`)
+fn multi_value_type(ty: &str) -> Option<&str> {
+    inner_type(ty, "Vec").or_else(|| inner_type(ty, "Option").and_then(|t| inner_type(t, "Vec")))
+}
+
+// Returns an error if the type of a field can't be built from arguments
+fn check_type(field: &Field) -> Result<(), String> {
+    let unsupported: bool = if field.positional {
+        multi_value_type(&field.ty).is_some()
+    } else {
+        inner_type(&field.ty, "Option").is_some_and(|t| inner_type(t, "Option").is_some())
+            || inner_type(&field.ty, "Vec").is_some_and(|t| multi_value_type(t).is_some())
+    };
+    if unsupported {
+        Err(format!(
+            "#[derive(Argpars)] does not support the type `{}` of field `{}`",
+            field.ty, field.name
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+// Returns true if a field has to be passed: when marked as required, or when it has no default and isn't
+// a flag, optional or taking multiple parameters
+fn is_required(field: &Field) -> bool {
+    field.required
+        || (field.default.is_none()
+            && field.ty != "bool"
+            && multi_value_type(&field.ty).is_none()
+            && inner_type(&field.ty, "Option").is_none())
+}
+
+// Returns the name of the argument of a field (`--dry-run` for `dry_run`, `INPUT` for a positional `input`)
+fn argument_name(field: &Field) -> String {
+    let name: &str = field.name.trim_start_matches("r#");
+    if field.positional {
+        name.to_uppercase()
+    } else {
+        format!("--{}", name.replace('_', "-"))
+    }
+}
+
+// Returns the code registering a field as an argument
+fn register_field(field: &Field) -> String {
+    let name: String = argument_name(field);
+    if field.positional {
+        let method: &str = if is_required(field) {
+            "add_required_positional"
+        } else {
+            "add_positional"
+        };
+        return format!("args.{}({:?}, {:?});\n", method, name, field.description);
+    }
+    let mut arg: String = format!("::argpars::Arg::new({:?}, {:?})", name, field.description);
+    if let Some(short) = &field.short {
+        arg.push_str(&format!(".alias({:?})", short));
+    }
+    if field.ty == "bool" {
+        arg.push_str(".takes_value(false)");
+    } else {
+        arg.push_str(".takes_value(true)");
+    }
+    if multi_value_type(&field.ty).is_some() {
+        arg.push_str(".multi_value(true)");
+    }
+    if is_required(field) {
+        arg.push_str(".required(true)");
+    }
+    if let Some(default) = &field.default {
+        arg.push_str(&format!(".default({:?})", default));
+    }
+    if field.hidden {
+        arg.push_str(".hidden(true)");
+    }
+    if let Some(delimiter) = field.delimiter.as_ref().and_then(|d| d.chars().next()) {
+        arg.push_str(&format!(".delimiter({:?})", delimiter));
+    }
+    if field.negatable {
+        arg.push_str(".negatable(true)");
+    }
+    if let Some(group) = &field.group {
+        arg.push_str(&format!(".group({:?})", group));
+    }
+    if let Some(value_name) = &field.value_name {
+        arg.push_str(&format!(".value_name({:?})", value_name));
+    }
+    format!("args.add_arg({});\n", arg)
+}
+
+// Returns the code converting a single value into a given type, as a `Result`
+fn convert(name: &str, value: &str, ty: &str) -> String {
+    format!(
+        "{value}.parse::<{ty}>().map_err(|_| ::argpars::ArgparsError::InvalidValue({name:?}.to_string(), {value}.to_string()))"
+    )
+}
+
+// Returns the code retriving the value of a field
+fn build_field(field: &Field) -> String {
+    let name: String = argument_name(field);
+    if field.positional {
+        let value: String = format!("args.get_positional({:?})", name);
+        return match inner_type(&field.ty, "Option") {
+            Some(inner) => format!(
+                "if {value}.is_empty() {{ ::std::option::Option::None }} else {{ ::std::option::Option::Some({}?) }}",
+                convert(&name, &value, inner)
+            ),
+            None => format!("{}?", convert(&name, &value, &field.ty)),
+        };
+    }
+    if field.ty == "bool" {
+        return format!("args.flag_state({:?}).unwrap_or(false)", name);
+    }
+    if let Some(inner) = inner_type(&field.ty, "Vec") {
+        return format!(
+            "args.get_parameters_for({name:?}).into_iter().map(|value| {}).collect::<::std::result::Result<Vec<{inner}>, ::argpars::ArgparsError>>()?",
+            convert(&name, "value", inner)
+        );
+    }
+    if let Some(inner) = multi_value_type(&field.ty) {
+        return format!(
+            "if args.get_parameters_for({name:?}).is_empty() {{ ::std::option::Option::None }} else {{ ::std::option::Option::Some(args.get_parameters_for({name:?}).into_iter().map(|value| {}).collect::<::std::result::Result<Vec<{inner}>, ::argpars::ArgparsError>>()?) }}",
+            convert(&name, "value", inner)
+        );
+    }
+    let value: String = format!("args.get_parameter_for({:?})", name);
+    match inner_type(&field.ty, "Option") {
+        Some(inner) => format!(
+            "if {value}.is_empty() {{ ::std::option::Option::None }} else {{ ::std::option::Option::Some({}?) }}",
+            convert(&name, &value, inner)
+        ),
+        None => format!("{}?", convert(&name, &value, &field.ty)),
+    }
+}
@@ -0,0 +1,58 @@
+//! Benchmark of registering arguments and looking them up in a CLI with hundreds of options
+//!
+//! Run with `cargo bench --bench lookup`
+
+use argpars::*;
+use std::time::{Duration, Instant};
+
+// Number of registered options, each of them passed with a parameter
+const OPTIONS: usize = 300;
+
+// Returns the mean time of running a function a given number of times
+fn measure<F: FnMut()>(iterations: u32, mut function: F) -> Duration {
+    let start: Instant = Instant::now();
+    for _ in 0..iterations {
+        function();
+    }
+    start.elapsed() / iterations
+}
+
+// Returns an app with OPTIONS registered options, all of them passed
+fn app() -> ArgsObj {
+    let mut arguments: Vec<String> = vec!["app".to_string()];
+    for i in 0..OPTIONS {
+        arguments.push(format!("--option-{}", i));
+        arguments.push(format!("value-{}", i));
+    }
+    let mut args: ArgsObj = Argpars::from_vec(arguments);
+    for i in 0..OPTIONS {
+        args.add_arg(Arg::new(&format!("--option-{}", i), "an option").takes_value(true));
+    }
+    args
+}
+
+fn main() {
+    let registration: Duration = measure(5, || {
+        app();
+    });
+    let args: ArgsObj = app();
+    let names: Vec<String> = (0..OPTIONS).map(|i| format!("--option-{}", i)).collect();
+    let passed: Duration = measure(20, || {
+        for name in &names {
+            assert!(args.passed(name));
+        }
+    });
+    let parameters: Duration = measure(20, || {
+        for name in &names {
+            assert!(!args.get_parameter_for(name).is_empty());
+        }
+    });
+    let parsing: Duration = measure(5, || {
+        assert!(args.try_pars().is_ok());
+    });
+    println!("{} options, each passed with a parameter:", OPTIONS);
+    println!("  registering all options:          {:?}", registration);
+    println!("  passed() for every option:        {:?}", passed);
+    println!("  get_parameter_for() every option: {:?}", parameters);
+    println!("  try_pars():                       {:?}", parsing);
+}
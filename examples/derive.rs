@@ -0,0 +1,27 @@
+use argpars::*;
+
+#[derive(Argpars)]
+struct Cli {
+    /// display "stuff"
+    #[argpars(short = "-s")]
+    print_stuff: bool,
+    /// display whatever you pass as an parameter
+    print_param: Option<String>,
+    /// number of times to repeat the output
+    #[argpars(default = "1")]
+    repeat: u32,
+}
+
+fn main() {
+    // Building the Cli struct from the passed arguments (help, version and errors are handled automatically)
+    let cli: Cli = Cli::parse();
+
+    for _ in 0..cli.repeat {
+        if cli.print_stuff {
+            println!("stuff");
+        }
+        if let Some(param) = &cli.print_param {
+            println!("{}", param);
+        }
+    }
+}
@@ -0,0 +1,279 @@
+//! Shell completion scripts generation and dynamic completion of command lines
+
+use crate::{response_file, Arg, ArgsObj};
+
+/// Kind of values an argument takes, used for completing them and naming them in the help screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueHint {
+    FilePath,
+    DirPath,
+    Hostname,
+    /// Value which can't be completed, with its name (e.g. `Other("NUM")`)
+    Other(&'static str),
+}
+
+impl ValueHint {
+    // Returns the name of the value shown in the help screen
+    pub(crate) fn placeholder(&self) -> &str {
+        match self {
+            ValueHint::FilePath => "FILE",
+            ValueHint::DirPath => "DIR",
+            ValueHint::Hostname => "HOST",
+            ValueHint::Other(name) => name,
+        }
+    }
+}
+
+/// Shells for which completion scripts can be generated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl Shell {
+    // Returns the shell with a given name (`bash`, `zsh` or `fish`)
+    pub(crate) fn from_name(name: &str) -> Option<Shell> {
+        match name {
+            "bash" => Some(Shell::Bash),
+            "zsh" => Some(Shell::Zsh),
+            "fish" => Some(Shell::Fish),
+            _ => None,
+        }
+    }
+}
+
+// Returns the completion script for a given shell
+pub(crate) fn generate(shell: Shell, program: &str, args: &[Arg]) -> String {
+    match shell {
+        Shell::Bash => generate_bash(program, args),
+        Shell::Zsh => generate_zsh(program, args),
+        Shell::Fish => generate_fish(program, args),
+    }
+}
+
+// Returns all names (aliases and the name itself) of the given arguments
+fn all_names(args: &[Arg]) -> Vec<&str> {
+    let mut names: Vec<&str> = Vec::new();
+    for arg in args {
+        names.extend(arg.aliases.iter().map(|a| a.as_str()));
+        names.push(&arg.name);
+    }
+    names
+}
+
+// Returns the program name usable as a shell function identifier
+fn function_name(program: &str) -> String {
+    program
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn generate_bash(program: &str, args: &[Arg]) -> String {
+    let function: String = function_name(program);
+    let mut script: String = String::new();
+    script.push_str(&format!("_{}() {{\n", function));
+    script.push_str("    local cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+    script.push_str("    local prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n");
+    let hinted: Vec<&Arg> = args.iter().filter(|arg| arg.value_hint.is_some()).collect();
+    if !hinted.is_empty() {
+        script.push_str("    case \"$prev\" in\n");
+        for arg in hinted {
+            let names: Vec<&str> = arg
+                .aliases
+                .iter()
+                .chain(std::iter::once(&arg.name))
+                .map(|name| name.as_str())
+                .collect();
+            let action: &str = match arg.value_hint {
+                Some(ValueHint::FilePath) => "COMPREPLY=($(compgen -f -- \"$cur\"))",
+                Some(ValueHint::DirPath) => "COMPREPLY=($(compgen -d -- \"$cur\"))",
+                Some(ValueHint::Hostname) => "COMPREPLY=($(compgen -A hostname -- \"$cur\"))",
+                _ => "COMPREPLY=()",
+            };
+            script.push_str(&format!(
+                "        {})\n            {}\n            return\n            ;;\n",
+                names.join("|"),
+                action
+            ));
+        }
+        script.push_str("    esac\n");
+    }
+    script.push_str(&format!(
+        "    COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n",
+        all_names(args).join(" ")
+    ));
+    script.push_str("}\n");
+    script.push_str(&format!(
+        "complete -o default -F _{} {}\n",
+        function, program
+    ));
+    script
+}
+
+fn generate_zsh(program: &str, args: &[Arg]) -> String {
+    let mut script: String = String::new();
+    script.push_str(&format!("#compdef {}\n\n", program));
+    script.push_str("_arguments \\\n");
+    for arg in args {
+        let description: String = arg
+            .description
+            .trim()
+            .replace('\'', "'\\''")
+            .replace('[', "\\[")
+            .replace(']', "\\]");
+        let value: String = if !arg.choices.is_empty() {
+            format!(":value:({})", arg.choices.join(" ").replace('\'', "'\\''"))
+        } else if let Some(hint) = arg.value_hint {
+            match hint {
+                ValueHint::FilePath => ":file:_files".to_string(),
+                ValueHint::DirPath => ":directory:_files -/".to_string(),
+                ValueHint::Hostname => ":host:_hosts".to_string(),
+                ValueHint::Other(name) => format!(":{}: ", name.replace('\'', "'\\''")),
+            }
+        } else if arg.takes_value {
+            ":value:_files".to_string()
+        } else {
+            String::new()
+        };
+        for name in arg.aliases.iter().chain(std::iter::once(&arg.name)) {
+            script.push_str(&format!("    '{}[{}]{}' \\\n", name, description, value));
+        }
+    }
+    script.push_str("    '*::arg:_files'\n");
+    script
+}
+
+fn generate_fish(program: &str, args: &[Arg]) -> String {
+    let mut script: String = String::new();
+    for arg in args {
+        for name in arg.aliases.iter().chain(std::iter::once(&arg.name)) {
+            let option: String = if let Some(long) = name.strip_prefix("--") {
+                format!("-l {}", long)
+            } else if name.chars().count() == 2 {
+                format!("-s {}", name.chars().skip(1).collect::<String>())
+            } else {
+                format!("-o {}", name.trim_start_matches('-'))
+            };
+            let value: String = if !arg.choices.is_empty() {
+                format!(" -x -a '{}'", arg.choices.join(" ").replace('\'', "\\'"))
+            } else if let Some(hint) = arg.value_hint {
+                match hint {
+                    ValueHint::FilePath => " -r -F".to_string(),
+                    ValueHint::DirPath => " -x -a '(__fish_complete_directories)'".to_string(),
+                    ValueHint::Hostname => " -x -a '(__fish_print_hostnames)'".to_string(),
+                    ValueHint::Other(_) => " -x".to_string(),
+                }
+            } else if arg.takes_value {
+                " -r".to_string()
+            } else {
+                String::new()
+            };
+            script.push_str(&format!(
+                "complete -c {} {}{} -d '{}'\n",
+                program,
+                option,
+                value,
+                arg.description.trim().replace('\'', "\\'")
+            ));
+        }
+    }
+    script
+}
+
+// Returns candidates (with their descriptions) for the word under the cursor of a command line,
+// where the cursor is given as a number of characters
+pub(crate) fn candidates(args: &ArgsObj, line: &str, point: usize) -> Vec<(String, String)> {
+    let before_cursor: String = line.chars().take(point).collect();
+    let mut words: Vec<String> = response_file::split(&before_cursor);
+    let current: String = if before_cursor.ends_with(char::is_whitespace) {
+        String::new()
+    } else {
+        words.pop().unwrap_or_default()
+    };
+    // The first word is the program name
+    let previous: Option<&Arg> = words
+        .iter()
+        .skip(1)
+        .last()
+        .and_then(|word| args.args.iter().find(|arg| arg.matches(word)));
+    if let Some(arg) = previous.filter(|arg| arg.takes_value) {
+        if !arg.choices.is_empty() {
+            return arg
+                .choices
+                .iter()
+                .filter(|choice| choice.starts_with(&current))
+                .map(|choice| (choice.clone(), String::new()))
+                .collect();
+        }
+        return match arg.value_hint {
+            Some(ValueHint::FilePath) => paths(&current, false),
+            Some(ValueHint::DirPath) => paths(&current, true),
+            _ => Vec::new(),
+        };
+    }
+    if !current.starts_with('-') {
+        return Vec::new();
+    }
+    let mut candidates: Vec<(String, String)> = Vec::new();
+    for arg in args.args.iter().filter(|arg| !arg.hidden) {
+        for name in arg.aliases.iter().chain(std::iter::once(&arg.name)) {
+            if name.starts_with(&current) {
+                candidates.push((name.clone(), arg.description.trim().to_string()));
+            }
+        }
+    }
+    candidates
+}
+
+// Returns paths starting with a given prefix, only directories if requested (which end with a '/')
+fn paths(prefix: &str, directories_only: bool) -> Vec<(String, String)> {
+    let (directory, name) = match prefix.rfind('/') {
+        Some(end) => (&prefix[..end + 1], &prefix[end + 1..]),
+        None => ("", prefix),
+    };
+    let entries = match std::fs::read_dir(if directory.is_empty() { "." } else { directory }) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    let mut paths: Vec<(String, String)> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let file_name: String = entry.file_name().to_string_lossy().to_string();
+            let is_directory: bool = entry.file_type().is_ok_and(|t| t.is_dir());
+            // Hidden files are only completed when asked for explicitly
+            let hidden: bool = file_name.starts_with('.') && !name.starts_with('.');
+            if !file_name.starts_with(name) || hidden || (directories_only && !is_directory) {
+                return None;
+            }
+            let suffix: &str = if is_directory { "/" } else { "" };
+            Some((
+                format!("{}{}{}", directory, file_name, suffix),
+                String::new(),
+            ))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+// Returns candidates in the format read by a given shell, one per line
+pub(crate) fn format_candidates(shell: Shell, candidates: &[(String, String)]) -> String {
+    let mut output: String = String::new();
+    for (candidate, description) in candidates {
+        let line: String = match shell {
+            Shell::Bash => candidate.clone(),
+            Shell::Zsh if !description.is_empty() => {
+                format!("{}:{}", candidate.replace(':', "\\:"), description)
+            }
+            Shell::Zsh => candidate.replace(':', "\\:"),
+            Shell::Fish if !description.is_empty() => format!("{}\t{}", candidate, description),
+            Shell::Fish => candidate.clone(),
+        };
+        output.push_str(&line);
+        output.push('\n');
+    }
+    output
+}
@@ -0,0 +1,48 @@
+//! Simple INI-style config files parsing
+
+use std::collections::HashMap;
+
+// Returns a path with a leading `~` replaced by the home directory
+pub(crate) fn expand_home(path: &str) -> String {
+    match (path.strip_prefix('~'), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{}{}", home, rest)
+        }
+        _ => path.to_string(),
+    }
+}
+
+// Returns key=value pairs of a config file, with keys converted into argument names (`port` becomes `--port`)
+//
+// Empty lines, comments (starting with `#` or `;`) and `[section]` headers are skipped,
+// and values can be surrounded by quotes
+pub(crate) fn parse(content: &str) -> HashMap<String, String> {
+    let mut values: HashMap<String, String> = HashMap::new();
+    for line in content.lines() {
+        let line: &str = line.trim();
+        if line.is_empty()
+            || line.starts_with('#')
+            || line.starts_with(';')
+            || (line.starts_with('[') && line.ends_with(']'))
+        {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let key: &str = key.trim();
+            let mut value: &str = value.trim();
+            if value.len() >= 2
+                && ((value.starts_with('"') && value.ends_with('"'))
+                    || (value.starts_with('\'') && value.ends_with('\'')))
+            {
+                value = &value[1..value.len() - 1];
+            }
+            let key: String = if key.starts_with('-') {
+                key.to_string()
+            } else {
+                format!("--{}", key)
+            };
+            values.insert(key, value.to_string());
+        }
+    }
+    values
+}
@@ -0,0 +1,140 @@
+//! JSON description of the command line interface
+
+use crate::{Argpars, ArgparsError, ArgsObj, REDACTED};
+
+// Returns text as a JSON string literal
+fn string(text: &str) -> String {
+    let mut escaped: String = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+// Returns an optional text as a JSON string literal or null
+fn optional_string(text: Option<&str>) -> String {
+    text.map_or("null".to_string(), string)
+}
+
+// Returns texts as a JSON array of strings
+fn strings(texts: &[String]) -> String {
+    let texts: Vec<String> = texts.iter().map(|t| string(t)).collect();
+    format!("[{}]", texts.join(", "))
+}
+
+// Returns JSON objects as an array, one per line
+fn objects(objects: &[String]) -> String {
+    if objects.is_empty() {
+        "[]".to_string()
+    } else {
+        format!("[\n{}\n  ]", objects.join(",\n"))
+    }
+}
+
+// Returns the description of the app, its arguments (except hidden ones) and positional arguments in JSON
+pub(crate) fn generate(args: &ArgsObj, program: &str) -> String {
+    let arguments: Vec<String> = args
+        .args
+        .iter()
+        .filter(|arg| !arg.hidden)
+        .map(|arg| {
+            format!(
+                "    {{\n      \"name\": {},\n      \"aliases\": {},\n      \"description\": {},\n      \"takes_value\": {},\n      \"multi_value\": {},\n      \"required\": {},\n      \"default\": {},\n      \"env\": {},\n      \"choices\": {},\n      \"group\": {},\n      \"value_hint\": {},\n      \"value_name\": {},\n      \"secret\": {}\n    }}",
+                string(&arg.name),
+                strings(&arg.aliases),
+                string(arg.description.trim()),
+                arg.takes_value,
+                arg.multi_value,
+                arg.required,
+                // The default of a secret argument is not exported
+                optional_string(arg.default.as_deref().map(|d| if arg.secret { REDACTED } else { d })),
+                optional_string(arg.env.as_deref()),
+                strings(&arg.choices),
+                optional_string(arg.group.as_deref()),
+                optional_string(arg.value_hint.as_ref().map(|hint| hint.placeholder())),
+                optional_string(arg.value_name.as_deref()),
+                arg.secret
+            )
+        })
+        .collect();
+    let positionals: Vec<String> = args
+        .positionals
+        .iter()
+        .map(|positional| {
+            format!(
+                "    {{\n      \"name\": {},\n      \"description\": {},\n      \"required\": {}\n    }}",
+                string(&positional.name),
+                string(positional.description.trim()),
+                positional.required
+            )
+        })
+        .collect();
+    format!(
+        "{{\n  \"program\": {},\n  \"name\": {},\n  \"description\": {},\n  \"version\": {},\n  \"usage\": {},\n  \"arguments\": {},\n  \"positionals\": {}\n}}\n",
+        string(program),
+        string(&args.help_name),
+        string(&args.help_description),
+        string(&args.help_version),
+        string(args.usage().trim()),
+        objects(&arguments),
+        objects(&positionals)
+    )
+}
+
+// Returns the passed token (or the argument) an error is about, empty if there is none
+pub(crate) fn error_token(err: &ArgparsError) -> &str {
+    match err {
+        ArgparsError::UnknownOption(token)
+        | ArgparsError::AmbiguousOption(token, _)
+        | ArgparsError::MissingValue(token)
+        | ArgparsError::MissingRequiredArgument(token)
+        | ArgparsError::MissingRequiredPositional(token)
+        | ArgparsError::InvalidValue(_, token)
+        | ArgparsError::InvalidChoice(_, token, _)
+        | ArgparsError::OutOfRange(_, token, _)
+        | ArgparsError::ConflictingOptions(_, token)
+        | ArgparsError::MissingDependency(token, _)
+        | ArgparsError::UnexpectedValue(token) => token,
+        ArgparsError::WrongNumberOfPositionals(_, _) => "",
+    }
+}
+
+// Returns an error as a single-line JSON object
+pub(crate) fn error(
+    err: &ArgparsError,
+    position: Option<usize>,
+    suggestion: Option<&str>,
+    message: &str,
+) -> String {
+    let kind: &str = match err {
+        ArgparsError::UnknownOption(_) => "unknown_option",
+        ArgparsError::AmbiguousOption(_, _) => "ambiguous_option",
+        ArgparsError::MissingValue(_) => "missing_value",
+        ArgparsError::MissingRequiredArgument(_) => "missing_required_argument",
+        ArgparsError::MissingRequiredPositional(_) => "missing_required_positional",
+        ArgparsError::InvalidValue(_, _) => "invalid_value",
+        ArgparsError::InvalidChoice(_, _, _) => "invalid_choice",
+        ArgparsError::OutOfRange(_, _, _) => "out_of_range",
+        ArgparsError::ConflictingOptions(_, _) => "conflicting_options",
+        ArgparsError::MissingDependency(_, _) => "missing_dependency",
+        ArgparsError::WrongNumberOfPositionals(_, _) => "wrong_number_of_positionals",
+        ArgparsError::UnexpectedValue(_) => "unexpected_value",
+    };
+    format!(
+        "{{\"kind\": {}, \"token\": {}, \"position\": {}, \"suggestion\": {}, \"message\": {}}}\n",
+        string(kind),
+        optional_string(Some(error_token(err)).filter(|token| !token.is_empty())),
+        position.map_or("null".to_string(), |p| p.to_string()),
+        optional_string(suggestion),
+        string(message)
+    )
+}
@@ -0,0 +1,324 @@
+//! Help screen layout: terminal width detection and word wrapping
+
+// Terminal width used when it can't be detected
+const DEFAULT_WIDTH: usize = 80;
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod ioctl {
+    #[repr(C)]
+    pub(super) struct WinSize {
+        pub(super) rows: u16,
+        pub(super) columns: u16,
+        pub(super) x_pixels: u16,
+        pub(super) y_pixels: u16,
+    }
+
+    // Linux uses the BSD value (`_IOR('t', 104, struct winsize)`) on PowerPC, MIPS and SPARC
+    #[cfg(all(
+        target_os = "linux",
+        not(any(
+            target_arch = "powerpc",
+            target_arch = "powerpc64",
+            target_arch = "mips",
+            target_arch = "mips64",
+            target_arch = "mips32r6",
+            target_arch = "mips64r6",
+            target_arch = "sparc",
+            target_arch = "sparc64"
+        ))
+    ))]
+    pub(super) const TIOCGWINSZ: std::ffi::c_ulong = 0x5413;
+    #[cfg(any(
+        target_os = "macos",
+        all(
+            target_os = "linux",
+            any(
+                target_arch = "powerpc",
+                target_arch = "powerpc64",
+                target_arch = "mips",
+                target_arch = "mips64",
+                target_arch = "mips32r6",
+                target_arch = "mips64r6",
+                target_arch = "sparc",
+                target_arch = "sparc64"
+            )
+        )
+    ))]
+    pub(super) const TIOCGWINSZ: std::ffi::c_ulong = 0x40087468;
+
+    extern "C" {
+        pub(super) fn ioctl(
+            fd: std::ffi::c_int,
+            request: std::ffi::c_ulong,
+            ...
+        ) -> std::ffi::c_int;
+    }
+}
+
+// Returns the width of the terminal connected to stdout, if there is one
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn detected_width() -> Option<usize> {
+    let mut size: ioctl::WinSize = ioctl::WinSize {
+        rows: 0,
+        columns: 0,
+        x_pixels: 0,
+        y_pixels: 0,
+    };
+    // SAFETY: TIOCGWINSZ only writes a WinSize struct into the given pointer
+    let result: std::ffi::c_int = unsafe { ioctl::ioctl(1, ioctl::TIOCGWINSZ, &mut size) };
+    if result == 0 && size.columns > 0 {
+        Some(size.columns as usize)
+    } else {
+        None
+    }
+}
+
+// Returns the height of the terminal connected to stdout, if there is one
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn detected_height() -> Option<usize> {
+    let mut size: ioctl::WinSize = ioctl::WinSize {
+        rows: 0,
+        columns: 0,
+        x_pixels: 0,
+        y_pixels: 0,
+    };
+    // SAFETY: TIOCGWINSZ only writes a WinSize struct into the given pointer
+    let result: std::ffi::c_int = unsafe { ioctl::ioctl(1, ioctl::TIOCGWINSZ, &mut size) };
+    if result == 0 && size.rows > 0 {
+        Some(size.rows as usize)
+    } else {
+        None
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn detected_width() -> Option<usize> {
+    None
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn detected_height() -> Option<usize> {
+    None
+}
+
+// Returns the terminal width: from the `COLUMNS` environment variable, the terminal itself or the default
+pub(crate) fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse::<usize>().ok())
+        .filter(|columns| *columns > 0)
+        .or_else(detected_width)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+// Returns the terminal height: from the `LINES` environment variable or the terminal itself, if it can be detected
+pub(crate) fn terminal_height() -> Option<usize> {
+    std::env::var("LINES")
+        .ok()
+        .and_then(|lines| lines.trim().parse::<usize>().ok())
+        .filter(|lines| *lines > 0)
+        .or_else(detected_height)
+}
+
+// Ranges of characters which take no space in a terminal: combining marks, zero width characters
+// and variation selectors
+const ZERO_WIDTH: &[(u32, u32)] = &[
+    (0x0300, 0x036F),
+    (0x0483, 0x0489),
+    (0x0591, 0x05BD),
+    (0x05BF, 0x05BF),
+    (0x05C1, 0x05C2),
+    (0x05C4, 0x05C5),
+    (0x05C7, 0x05C7),
+    (0x0610, 0x061A),
+    (0x064B, 0x065F),
+    (0x0670, 0x0670),
+    (0x06D6, 0x06DC),
+    (0x06DF, 0x06E4),
+    (0x06E7, 0x06E8),
+    (0x06EA, 0x06ED),
+    (0x0900, 0x0902),
+    (0x093A, 0x093A),
+    (0x093C, 0x093C),
+    (0x0941, 0x0948),
+    (0x094D, 0x094D),
+    (0x0951, 0x0957),
+    (0x0962, 0x0963),
+    (0x0E31, 0x0E31),
+    (0x0E34, 0x0E3A),
+    (0x0E47, 0x0E4E),
+    (0x1160, 0x11FF),
+    (0x1AB0, 0x1AFF),
+    (0x1DC0, 0x1DFF),
+    (0x200B, 0x200F),
+    (0x202A, 0x202E),
+    (0x2060, 0x2064),
+    (0x20D0, 0x20FF),
+    (0x302A, 0x302D),
+    (0x3099, 0x309A),
+    (0xFE00, 0xFE0F),
+    (0xFE20, 0xFE2F),
+    (0xFEFF, 0xFEFF),
+    (0xE0000, 0xE007F),
+    (0xE0100, 0xE01EF),
+];
+
+// Ranges of characters which take two columns in a terminal: CJK, Hangul, fullwidth forms and emoji
+const DOUBLE_WIDTH: &[(u32, u32)] = &[
+    (0x1100, 0x115F),
+    (0x231A, 0x231B),
+    (0x2329, 0x232A),
+    (0x23E9, 0x23EC),
+    (0x25FD, 0x25FE),
+    (0x2614, 0x2615),
+    (0x2648, 0x2653),
+    (0x26AA, 0x26AB),
+    (0x26BD, 0x26BE),
+    (0x26C4, 0x26C5),
+    (0x26F5, 0x26F5),
+    (0x26FA, 0x26FA),
+    (0x26FD, 0x26FD),
+    (0x2705, 0x2705),
+    (0x270A, 0x270B),
+    (0x2728, 0x2728),
+    (0x274C, 0x274C),
+    (0x2753, 0x2755),
+    (0x2757, 0x2757),
+    (0x2795, 0x2797),
+    (0x27B0, 0x27B0),
+    (0x27BF, 0x27BF),
+    (0x2B1B, 0x2B1C),
+    (0x2B50, 0x2B50),
+    (0x2B55, 0x2B55),
+    (0x2E80, 0x3029),
+    (0x302E, 0x303E),
+    (0x3041, 0x3098),
+    (0x309B, 0x33FF),
+    (0x3400, 0x4DBF),
+    (0x4E00, 0x9FFF),
+    (0xA000, 0xA4CF),
+    (0xA960, 0xA97F),
+    (0xAC00, 0xD7A3),
+    (0xF900, 0xFAFF),
+    (0xFE10, 0xFE19),
+    (0xFE30, 0xFE6F),
+    (0xFF00, 0xFF60),
+    (0xFFE0, 0xFFE6),
+    (0x16FE0, 0x16FE4),
+    (0x17000, 0x18CFF),
+    (0x1B000, 0x1B2FF),
+    (0x1F004, 0x1F004),
+    (0x1F0CF, 0x1F0CF),
+    (0x1F18E, 0x1F18E),
+    (0x1F191, 0x1F19A),
+    (0x1F200, 0x1F202),
+    (0x1F210, 0x1F23B),
+    (0x1F240, 0x1F248),
+    (0x1F250, 0x1F251),
+    (0x1F260, 0x1F265),
+    (0x1F300, 0x1F320),
+    (0x1F32D, 0x1F335),
+    (0x1F337, 0x1F37C),
+    (0x1F37E, 0x1F393),
+    (0x1F3A0, 0x1F3CA),
+    (0x1F3CF, 0x1F3D3),
+    (0x1F3E0, 0x1F3F0),
+    (0x1F3F4, 0x1F3F4),
+    (0x1F3F8, 0x1F43E),
+    (0x1F440, 0x1F440),
+    (0x1F442, 0x1F4FC),
+    (0x1F4FF, 0x1F53D),
+    (0x1F54B, 0x1F54E),
+    (0x1F550, 0x1F567),
+    (0x1F57A, 0x1F57A),
+    (0x1F595, 0x1F596),
+    (0x1F5A4, 0x1F5A4),
+    (0x1F5FB, 0x1F64F),
+    (0x1F680, 0x1F6C5),
+    (0x1F6CC, 0x1F6CC),
+    (0x1F6D0, 0x1F6D2),
+    (0x1F6D5, 0x1F6D7),
+    (0x1F6EB, 0x1F6EC),
+    (0x1F6F4, 0x1F6FC),
+    (0x1F7E0, 0x1F7EB),
+    (0x1F90C, 0x1F93A),
+    (0x1F93C, 0x1F945),
+    (0x1F947, 0x1F9FF),
+    (0x1FA70, 0x1FAFF),
+    (0x20000, 0x2FFFD),
+    (0x30000, 0x3FFFD),
+];
+
+// Returns true if a character is in one of the given sorted ranges
+fn in_ranges(c: char, ranges: &[(u32, u32)]) -> bool {
+    let code: u32 = c as u32;
+    ranges
+        .binary_search_by(|&(start, end)| {
+            if end < code {
+                std::cmp::Ordering::Less
+            } else if start > code {
+                std::cmp::Ordering::Greater
+            } else {
+                std::cmp::Ordering::Equal
+            }
+        })
+        .is_ok()
+}
+
+// Returns the number of terminal columns a character takes
+pub(crate) fn char_width(c: char) -> usize {
+    if c.is_control() || in_ranges(c, ZERO_WIDTH) {
+        0
+    } else if in_ranges(c, DOUBLE_WIDTH) {
+        2
+    } else {
+        1
+    }
+}
+
+// Returns the number of terminal columns a string takes
+pub(crate) fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+// Returns text split into lines no wider than a given width (unless a single word is wider). Words
+// containing double width characters (which are usually written without spaces) are broken between
+// characters instead
+pub(crate) fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut line: String = String::new();
+    let mut line_width: usize = 0;
+    for word in text.split_whitespace() {
+        let word_width: usize = display_width(word);
+        let breakable: bool = word.chars().any(|c| char_width(c) == 2);
+        if !line.is_empty()
+            && line_width + 1 + word_width > width
+            && (!breakable || line_width + 2 >= width)
+        {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+        if !line.is_empty() {
+            line.push(' ');
+            line_width += 1;
+        }
+        if breakable && line_width + word_width > width {
+            for c in word.chars() {
+                let c_width: usize = char_width(c);
+                if line_width > 0 && line_width + c_width > width {
+                    lines.push(std::mem::take(&mut line));
+                    line_width = 0;
+                }
+                line.push(c);
+                line_width += c_width;
+            }
+        } else {
+            line.push_str(word);
+            line_width += word_width;
+        }
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
@@ -6,7 +6,10 @@
 //!
 //! Basic usage (checkout the examples/usage.rs file for more information)
 //!
-//! ```rust
+#![cfg_attr(feature = "std", doc = "```no_run")]
+#![cfg_attr(not(feature = "std"), doc = "```ignore")]
+//! use argpars::*;
+//!
 //! let mut args: ArgsObj = Argpars::new();
 //!
 //! // Setting basic info about the app
@@ -40,132 +43,1073 @@
 //!
 //! This project is distributed under MIT license.
 
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+#[cfg(feature = "std")]
 use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::ffi::{OsStr, OsString};
+#[cfg(feature = "std")]
+use std::fmt;
+#[cfg(feature = "std")]
+use std::io::{IsTerminal, Read, Write};
+#[cfg(feature = "std")]
+use std::ops::{Bound, RangeBounds};
+#[cfg(feature = "std")]
+use std::rc::Rc;
+#[cfg(feature = "std")]
+use std::str::FromStr;
 
-/// Returns vector of passed arguments
-fn get_args() -> Vec<String> {
-    std::env::args().collect::<Vec<String>>()
-}
+#[cfg(feature = "std")]
+mod completions;
+#[cfg(feature = "std")]
+mod config;
+#[cfg(feature = "std")]
+mod json;
+#[cfg(feature = "std")]
+mod layout;
+#[cfg(feature = "std")]
+mod man;
+#[cfg(feature = "std")]
+mod markdown;
+#[cfg(feature = "std")]
+mod messages;
+#[cfg(feature = "std")]
+mod pager;
+pub mod parser;
+#[cfg(feature = "std")]
+mod response_file;
+#[cfg(feature = "std")]
+mod style;
+#[cfg(feature = "std")]
+pub mod testing;
+
+#[cfg(feature = "std")]
+pub use completions::{Shell, ValueHint};
+#[cfg(feature = "std")]
+pub use messages::Messages;
+#[cfg(feature = "std")]
+pub use style::ColorChoice;
+
+#[cfg(feature = "derive")]
+pub use argpars_derive::Argpars;
 
 // Returns true if a vector contains given value
+#[cfg(feature = "std")]
 fn is_value_in_a_vector_str(value: &str, vector: &[String]) -> bool {
-    return vector.iter().any(|a| a == value);
+    vector.iter().any(|a| a == value)
+}
+
+/// Function used to split a command line into arguments like a shell would
+///
+/// Single and double quotes group words containing whitespace, and a backslash escapes
+/// the next character (except inside single quotes)
+///
+/// # Examples
+///
+/// ```
+/// let words: Vec<String> = argpars::split("add --message \"first commit\" 'a b'");
+/// assert_eq!(words, vec!["add", "--message", "first commit", "a b"]);
+/// ```
+#[cfg(feature = "std")]
+pub fn split(line: &str) -> Vec<String> {
+    response_file::split(line)
 }
 
+/// Function used to parse an argument vector (starting with the program name) against definitions of arguments
+///
+/// Unlike `try_pars()`, it only depends on its inputs: passed arguments stored in the definitions are ignored,
+/// environment variables (`env_fallback()`) are not read and nothing is printed, which makes it suitable for
+/// fuzzing and property tests
+///
+/// No argument vector makes it panic: invalid arguments result in an error
+///
+/// # Examples
+///
+/// ```
+/// use argpars::*;
+///
+/// let mut defs: ArgDefs = Argpars::from_vec(vec![]);
+/// defs.add_arg(Arg::new("--output", "file to write to").alias("-o").takes_value(true));
+/// let argv: Vec<String> = split("app -o out.txt");
+/// let matches: Matches = parse_tokens(&defs, &argv).unwrap();
+/// assert_eq!(matches.value_of("--output"), Some("out.txt"));
+/// assert!(parse_tokens(&defs, &split("app --outptu")).is_err());
+///
+/// // Adversarial arguments
+/// defs.add_arg(Arg::new("--héllo", "greet").takes_value(true));
+/// defs.allow_abbreviations(true);
+/// defs.windows_options(true);
+/// defs.trailing_positional("TARGET", "", false);
+/// let tokens = ["", "-", "--", "-o", "--h", "--ha", "--output=", "=", "/?", "/héllo:", "-é", "-5"];
+/// assert!(parse_tokens(&defs, &[]).is_ok());
+/// for first in tokens {
+///     for second in tokens {
+///         let _ = parse_tokens(&defs, &[first.to_string(), second.to_string()]);
+///         let _ = parse_tokens(&defs, &["app".to_string(), first.to_string(), second.to_string()]);
+///     }
+/// }
+/// ```
+#[cfg(feature = "std")]
+pub fn parse_tokens(defs: &ArgDefs, argv: &[String]) -> Result<Matches, ArgparsError> {
+    let mut args: ArgsObj = defs.clone();
+    for arg in &mut args.args {
+        arg.env = None;
+    }
+    args.arguments_passed_os = argv.iter().map(OsString::from).collect();
+    args.number_of_arguments = argv.len() as u32;
+    args.arguments_passed = argv.to_vec();
+    args.refresh_lookups();
+    args.try_pars()
+}
+
+// Replacement of parameters of secret arguments in output
+#[cfg(feature = "std")]
+const REDACTED: &str = "***";
+
 /// Argpars trait
+#[cfg(feature = "std")]
 pub trait Argpars {
     fn new() -> Self;
+    fn from_vec(arguments: Vec<String>) -> Self;
+    fn from_line(line: &str) -> Self;
+    fn from_os_args() -> Self;
+    fn from_os_vec(arguments: Vec<OsString>) -> Self;
+    fn from_spec(specs: &[Arg]) -> Self;
     fn no_arguments_passed(&self) -> bool;
     fn passed(&self, arg: &str) -> bool;
+    fn count_of(&self, arg: &str) -> usize;
+    fn verbosity(&self) -> i32;
+    fn flag_state(&self, arg: &str) -> Option<bool>;
+    fn flags_set(&self, flags: &[&str]) -> u32;
+    fn occurrences_of(&self, arg: &str) -> Vec<usize>;
+    fn index_of(&self, arg: &str) -> Option<usize>;
+    fn iter_args(&self) -> impl Iterator<Item = ArgInfo<'_>>;
+    fn definitions(&self) -> Definitions;
+    fn reset_to(&mut self, definitions: Definitions);
+    fn add_arg(&mut self, arg: Arg);
+    fn add_args(&mut self, specs: &[Arg]);
     fn add_argument(&mut self, argument: &str, description: &str);
+    fn add_argument_with_short(&mut self, short: &str, argument: &str, description: &str);
+    fn add_flag(&mut self, argument: &str, description: &str);
+    fn standard_verbosity_flags(&mut self);
+    fn add_required_argument(&mut self, argument: &str, description: &str);
+    fn add_hidden_argument(&mut self, argument: &str, description: &str);
+    fn add_prefix_argument(&mut self, prefix: &str, description: &str);
+    fn add_group(&mut self, group: &str);
+    fn add_multi_value_argument(&mut self, argument: &str, description: &str);
+    fn add_map_argument(&mut self, argument: &str, description: &str);
+    fn set_default(&mut self, argument: &str, value: &str);
+    fn add_choices(&mut self, argument: &str, choices: &[&str]);
+    fn set_delimiter(&mut self, argument: &str, delimiter: char);
+    fn set_value_hint(&mut self, argument: &str, hint: ValueHint);
+    fn set_value_name(&mut self, argument: &str, name: &str);
+    fn mark_secret(&mut self, argument: &str);
+    fn env_fallback(&mut self, argument: &str, variable: &str);
+    fn config_file(&mut self, path: &str) -> std::io::Result<()>;
+    fn expand_response_files(&mut self) -> std::io::Result<()>;
+    fn add_validator<F: Fn(&str) -> bool + 'static>(&mut self, argument: &str, validator: F);
+    fn allow_unknown(&mut self, allow: bool);
+    fn allow_abbreviations(&mut self, allow: bool);
+    fn ignore_case(&mut self, ignore: bool);
+    fn windows_options(&mut self, enable: bool);
+    fn help_command(&mut self, enable: bool);
+    fn strict_values(&mut self, enable: bool);
+    fn posix_mode(&mut self, enable: bool);
+    fn enable_trace(&mut self);
+    fn paginate_help(&mut self, paginate: bool);
+    fn help_order(&mut self, order: HelpOrder);
+    fn default_arguments_last(&mut self, last: bool);
+    fn positionals_range<R: RangeBounds<usize>>(&mut self, range: R);
+    fn positional_args(&self) -> Vec<&str>;
+    fn unknown_args(&self) -> Vec<&str>;
+    fn on<F: Fn(&[&str]) -> i32 + 'static>(&mut self, argument: &str, handler: F);
+    fn register_extension<E: ArgparsExtension + 'static>(&mut self, extension: E);
+    fn range_constraint<T, R>(&mut self, argument: &str, range: R)
+    where
+        T: FromStr + PartialOrd + fmt::Display + 'static,
+        R: RangeBounds<T> + 'static;
+    fn add_exclusive_group(&mut self, group: &[&str]);
+    fn requires(&mut self, argument: &str, required: &str);
+    fn required_if(&mut self, argument: &str, condition: &str);
+    fn conflicts_with(&mut self, argument: &str, conflicting: &str);
+    fn deprecate_alias(&mut self, alias: &str, replacement: &str);
+    fn add_positional(&mut self, name: &str, description: &str);
+    fn add_required_positional(&mut self, name: &str, description: &str);
+    fn trailing_positional(&mut self, name: &str, description: &str, required: bool);
+    fn get_positional(&self, name: &str) -> &str;
+    fn value_is_stdin(&self, arg: &str) -> bool;
+    fn read_value_or_stdin(&self, arg: &str) -> std::io::Result<String>;
+    fn trailing_args(&self) -> &[String];
     fn default_arguments_passed(&self) -> bool;
     fn wrong_arguments_passed(&self) -> bool;
+    fn wrong_arguments(&self) -> Vec<&str>;
+    fn first_wrong_argument(&self) -> Option<&str>;
     fn get_parameter_for(&self, arg: &str) -> &str;
-    fn display_error_message(&self, err_type: &str, additional: &str);
+    fn get_last_parameter_for(&self, arg: &str) -> &str;
+    fn get_parameter_os_for(&self, arg: &str) -> &OsStr;
+    fn try_get_parameter_for(&self, arg: &str) -> Option<&str>;
+    fn resolved_value(&self, arg: &str) -> Option<(&str, ValueSource)>;
+    fn get_parameters_for(&self, arg: &str) -> Vec<&str>;
+    fn prefix_values(&self, prefix: &str) -> Vec<(&str, &str)>;
+    fn get_map_for(&self, arg: &str) -> HashMap<String, String>;
+    fn to_map(&self) -> HashMap<String, Vec<String>>;
+    fn get_parameter_as<T: FromStr>(&self, arg: &str) -> Result<T, ParseError>
+    where
+        T::Err: fmt::Display;
+    fn display_error_message(&self, err: &ArgparsError);
     fn no_default_arguments(&mut self);
+    fn disable_default(&mut self, argument: &str);
     fn display_help_screen(&self);
+    fn render_help(&self, width: usize) -> String;
+    fn usage(&self) -> String;
+    fn set_help_template(&mut self, template: &str);
+    fn set_version_template(&mut self, template: &str);
+    fn display_version(&self);
+    fn add_help_topic(&mut self, topic: &str, content: &str);
+    fn display_help_topic(&self, topic: &str);
+    fn set_color(&mut self, choice: ColorChoice);
+    fn error_format(&mut self, format: ErrorFormat);
+    fn set_messages(&mut self, messages: Messages);
+    fn set_output<W: Write + 'static>(&mut self, writer: W);
+    fn set_error_output<W: Write + 'static>(&mut self, writer: W);
+    fn exit_codes(&mut self, codes: ExitCodes);
     fn add_help_section(&mut self, section: &str, content: &str);
+    fn set_before_help(&mut self, text: &str);
+    fn set_after_help(&mut self, text: &str);
+    fn generate_completions(&self, shell: Shell) -> String;
+    fn dynamic_completions(&mut self, enable: bool);
+    fn completion_candidates(&self, line: &str, point: usize) -> Vec<String>;
+    fn generate_man_page(&self) -> String;
+    fn to_json_spec(&self) -> String;
+    fn to_markdown(&self) -> String;
     fn pars(&self) -> i32;
+    fn try_pars(&self) -> Result<ParsedArgs, ArgparsError>;
+    fn partial_pars(&self, arguments: &[&str]) -> Matches;
+    fn parse_from(&self, arguments: &[&str]) -> Result<Matches, ArgparsError>;
+    fn parse(&self) -> Matches;
     fn lookup_update(&mut self);
+    fn debug_assert_valid(&self);
+}
+
+/// Trait for structs filled from the command line, implemented by `#[derive(Argpars)]` (`derive` feature)
+#[cfg(feature = "std")]
+pub trait FromArgs: Sized {
+    /// Function used to add arguments of the struct into the app
+    fn register(args: &mut ArgsObj);
+
+    /// Function used to build the struct from passed arguments
+    fn from_parsed(args: &ArgsObj) -> Result<Self, ArgparsError>;
+
+    /// Function used to build the struct from the process arguments
+    ///
+    /// Like `pars()`, it displays the help screen, version information or errors,
+    /// exiting from the app when it can't continue
+    fn parse() -> Self {
+        let mut args: ArgsObj = Argpars::new();
+        Self::register(&mut args);
+        let return_value: i32 = args.pars();
+        if return_value != 0 || (args.default_arguments && args.default_arguments_passed()) {
+            std::process::exit(return_value);
+        }
+        match Self::from_parsed(&args) {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                args.display_error_message(&err);
+                std::process::exit(args.exit_codes.code_for(&err));
+            }
+        }
+    }
+
+    /// Function used to build the struct from a given argument vector, without printing anything
+    fn try_parse_from(arguments: Vec<String>) -> Result<Self, ArgparsError> {
+        let mut args: ArgsObj = Argpars::from_vec(arguments);
+        Self::register(&mut args);
+        args.try_pars()?;
+        Self::from_parsed(&args)
+    }
+}
+
+/// Trait for modules of an app which contribute their own arguments, added with `register_extension()`
+#[cfg(feature = "std")]
+pub trait ArgparsExtension {
+    /// Function used to add arguments of the module into the app
+    fn register(&self, args: &mut ArgsObj);
+
+    /// Function called by `pars()` after successful parsing, with found arguments
+    fn handle(&self, _matches: &Matches) {}
 }
 
+/// Function checking if a parameter of an argument is valid
+#[cfg(feature = "std")]
+pub type Validator = Rc<dyn Fn(&str) -> bool>;
+
+/// Function called with parameters of a passed argument, returning an exit code
+#[cfg(feature = "std")]
+pub type Handler = Rc<dyn Fn(&[&str]) -> i32>;
+
+/// Bounds of a range restricting parameters of an argument (see `Argpars::range_constraint()`)
+#[cfg(feature = "std")]
+pub type RangeBoundaries = (Bound<String>, Bound<String>);
+
 /// ArgsObj struct
+///
+/// Clones share validators, handlers, extensions and writers set with `set_output()` and `set_error_output()`
+#[cfg(feature = "std")]
+#[derive(Clone)]
 pub struct ArgsObj {
-    pub arguments_passed_args: std::env::Args,
     pub arguments_passed: Vec<String>,
+    pub arguments_passed_os: Vec<OsString>,
+    pub arguments_expanded: Vec<String>,
+    pub trailing_arguments: Vec<String>,
     pub number_of_arguments: u32,
-    pub arguments: Vec<String>,
+    pub args: Vec<Arg>,
     pub default_arguments: bool,
+    pub disabled_default_arguments: Vec<String>,
     pub help_usage: String,
     pub help_name: String,
     pub help_description: String,
     pub help_version: String,
-    pub arg_desc_vec: Vec<String>,
     pub help_sections: Vec<String>,
     pub help_sections_content: Vec<String>,
+    pub help_template: Option<String>,
+    pub before_help: String,
+    pub after_help: String,
+    pub version_template: Option<String>,
+    pub help_topics: Vec<(String, String)>,
+    pub current_group: Option<String>,
     pub passed_arguments_lookup: HashMap<String, bool>,
     pub parameters_lookup: HashMap<String, String>,
+    pub parameters_list_lookup: HashMap<String, Vec<String>>,
+    pub environment_lookup: HashMap<String, String>,
+    pub config_values: HashMap<String, String>,
+    pub validators: Vec<(String, Validator)>,
+    pub handlers: Vec<(String, Handler)>,
+    pub extensions: Vec<Rc<dyn ArgparsExtension>>,
+    pub range_constraints: Vec<(String, RangeBoundaries, Validator)>,
+    pub exclusive_groups: Vec<Vec<String>>,
+    pub requirements: Vec<(String, String)>,
+    pub conditional_requirements: Vec<(String, String)>,
+    pub deprecated_aliases: Vec<(String, String)>,
+    pub positionals: Vec<Positional>,
+    pub color: ColorChoice,
+    pub error_format: ErrorFormat,
+    pub exit_codes: ExitCodes,
+    pub allow_unknown: bool,
+    pub allow_abbreviations: bool,
+    pub ignore_case: bool,
+    pub windows_options: bool,
+    pub help_command: bool,
+    pub strict_values: bool,
+    pub posix_mode: bool,
+    pub trace: bool,
+    pub paginate_help: bool,
+    pub help_order: HelpOrder,
+    pub default_arguments_last: bool,
+    pub positionals_range: (Bound<usize>, Bound<usize>),
+    pub dynamic_completions: bool,
+    pub messages: Messages,
+    #[deprecated(note = "use `trailing_positional()` instead")]
     pub last_param_ok: bool,
+    pub(crate) output: Option<Rc<RefCell<dyn Write>>>,
+    pub(crate) error_output: Option<Rc<RefCell<dyn Write>>>,
+    pub(crate) argument_names: HashMap<String, usize>,
+    pub(crate) occurrences: HashMap<String, Vec<usize>>,
+    pub(crate) options_end: usize,
+    pub(crate) preceding_options: Vec<usize>,
+    pub(crate) compound_tokens: Vec<usize>,
 }
 
-/// Implementation of Argpars for the ArgsObj struct
-impl Argpars for ArgsObj {
-    /// ArgsObj constructor
+#[cfg(feature = "std")]
+impl fmt::Debug for ArgsObj {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let validators: Vec<&String> = self.validators.iter().map(|(arg, _)| arg).collect();
+        let handlers: Vec<&String> = self.handlers.iter().map(|(arg, _)| arg).collect();
+        let range_constraints: Vec<(&String, &RangeBoundaries)> = self
+            .range_constraints
+            .iter()
+            .map(|(arg, range, _)| (arg, range))
+            .collect();
+        f.debug_struct("ArgsObj")
+            .field("arguments_passed", &self.redacted_passed())
+            .field("arguments_expanded", &self.redacted_expanded())
+            .field("trailing_arguments", &self.trailing_arguments)
+            .field("args", &self.args)
+            .field("positionals", &self.positionals)
+            .field("default_arguments", &self.default_arguments)
+            .field(
+                "disabled_default_arguments",
+                &self.disabled_default_arguments,
+            )
+            .field("help_usage", &self.help_usage)
+            .field("help_name", &self.help_name)
+            .field("help_description", &self.help_description)
+            .field("help_version", &self.help_version)
+            .field("help_sections", &self.help_sections)
+            .field("before_help", &self.before_help)
+            .field("after_help", &self.after_help)
+            .field("help_topics", &self.help_topics)
+            .field(
+                "environment_lookup",
+                &self.redacted_values(&self.environment_lookup),
+            )
+            .field("config_values", &self.redacted_values(&self.config_values))
+            .field("validators", &validators)
+            .field("handlers", &handlers)
+            .field("extensions", &self.extensions.len())
+            .field("range_constraints", &range_constraints)
+            .field("exclusive_groups", &self.exclusive_groups)
+            .field("requirements", &self.requirements)
+            .field("conditional_requirements", &self.conditional_requirements)
+            .field("deprecated_aliases", &self.deprecated_aliases)
+            .field("color", &self.color)
+            .field("error_format", &self.error_format)
+            .field("exit_codes", &self.exit_codes)
+            .field("allow_unknown", &self.allow_unknown)
+            .field("allow_abbreviations", &self.allow_abbreviations)
+            .field("ignore_case", &self.ignore_case)
+            .field("windows_options", &self.windows_options)
+            .field("help_command", &self.help_command)
+            .field("strict_values", &self.strict_values)
+            .field("posix_mode", &self.posix_mode)
+            .field("trace", &self.trace)
+            .field("help_order", &self.help_order)
+            .field("default_arguments_last", &self.default_arguments_last)
+            .field("positionals_range", &self.positionals_range)
+            .field("dynamic_completions", &self.dynamic_completions)
+            .finish_non_exhaustive()
+    }
+}
+
+/// ArgsObj with default arguments only, an empty program name and no passed arguments
+#[cfg(feature = "std")]
+impl Default for ArgsObj {
+    fn default() -> ArgsObj {
+        Argpars::from_vec(vec![String::new()])
+    }
+}
+
+/// Description of a single argument of the app
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Arg {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub description: String,
+    pub takes_value: bool,
+    pub multi_value: bool,
+    pub required: bool,
+    pub default: Option<String>,
+    pub env: Option<String>,
+    pub hidden: bool,
+    pub group: Option<String>,
+    pub negatable: bool,
+    pub choices: Vec<String>,
+    pub delimiter: Option<char>,
+    pub value_hint: Option<ValueHint>,
+    pub value_name: Option<String>,
+    pub prefix: bool,
+    pub display_order: Option<usize>,
+    pub flag: bool,
+    pub key_value: bool,
+    pub secret: bool,
+}
+
+#[cfg(feature = "std")]
+impl Arg {
+    /// Arg constructor
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// use argpars::*;
     ///
-    /// let mut args: ArgsObj = Argpars::new();
+    /// let arg: Arg = Arg::new("--port", "port to listen on")
+    ///     .alias("-p")
+    ///     .takes_value(true)
+    ///     .default("8080");
     /// ```
-    fn new() -> ArgsObj {
-        ArgsObj {
-            arguments_passed_args: std::env::args(),
-            arguments_passed: get_args(),
-            number_of_arguments: std::env::args().len() as u32,
-            arguments: vec!["--help".to_string(), "--version".to_string()],
-            default_arguments: true,
-            help_usage: format!("Usage: {} [OPTION]...\n", get_args()[0]),
-            help_name: "Default name".to_string(),
-            help_description: "Default description".to_string(),
-            help_version: "Default version".to_string(),
-            arg_desc_vec: vec![
-                "--help".to_string(),
-                "\tdisplay this help and exit".to_string(),
-                "--version".to_string(),
-                "output version information and exit".to_string(),
-            ],
-            help_sections: Vec::new(),
-            help_sections_content: Vec::new(),
-            passed_arguments_lookup: HashMap::from([
-                ("--help".to_string(), false),
-                ("--version".to_string(), false),
-            ]),
-            parameters_lookup: HashMap::from([
-                ("--help".to_string(), "".to_string()),
-                ("--version".to_string(), "".to_string()),
-            ]),
-            last_param_ok: false,
+    pub fn new(name: &str, description: &str) -> Arg {
+        Arg {
+            name: name.to_string(),
+            description: description.to_string(),
+            ..Default::default()
         }
     }
 
-    /// Function which updates lookup HashMaps such as passed_arguments_lookup or parameters_lookup
-    fn lookup_update(&mut self) {
-        for arg in &self.arguments {
-            if self.arguments_passed.contains(arg) {
-                *self.passed_arguments_lookup.get_mut(arg).unwrap() = true;
-                *self.parameters_lookup.get_mut(arg).unwrap() =
-                    self.get_parameter_for(arg).to_string();
-            }
+    /// Function used to add an alias (e.g. a short form) of the argument
+    pub fn alias(mut self, alias: &str) -> Arg {
+        self.aliases.push(alias.to_string());
+        self
+    }
+
+    /// Function used to declare whether the argument expects a parameter
+    ///
+    /// Passing an argument declared with `takes_value(true)` without a parameter (at the end of passed
+    /// arguments or right before another argument) is a `MissingValue` error.
+    /// Arguments declared with `takes_value(false)` are flags: the token following them is never
+    /// taken as their parameter and `--flag=value` is rejected. Arguments which declare neither
+    /// take the token following them as a parameter, unless it's another argument
+    pub fn takes_value(mut self, takes_value: bool) -> Arg {
+        self.takes_value = takes_value;
+        self.flag = !takes_value;
+        self
+    }
+
+    /// Function used to mark the argument as accepting multiple parameters
+    pub fn multi_value(mut self, multi_value: bool) -> Arg {
+        self.multi_value = multi_value;
+        if multi_value {
+            self.takes_value = true;
         }
+        self
     }
 
-    /// Function which, when called, disables default arguments (--help, --version, ...)
+    /// Function used to mark the argument as taking `KEY=VALUE` parameters (e.g. `--env KEY=VALUE`),
+    /// collected by `get_map_for()`
+    ///
+    /// A parameter without a `=` character is an `InvalidValue` error
+    pub fn key_value(mut self, key_value: bool) -> Arg {
+        self.key_value = key_value;
+        if key_value {
+            self.takes_value = true;
+        }
+        self
+    }
+
+    /// Function used to mark the parameter of the argument as sensitive (e.g. a password), replacing it
+    /// with `***` in error messages, trace output, `Debug` output and exports
     ///
     /// # Examples
     ///
-    /// ```no_run
+    /// ```
     /// use argpars::*;
     ///
-    /// let mut args: ArgsObj = Argpars::new();
-    /// args.no_default_arguments();
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app"));
+    /// args.add_arg(Arg::new("--password", "password to log in with").secret(true));
+    /// args.set_default("--password", "hunter2");
+    /// assert!(!args.render_help(80).contains("hunter2"));
+    /// assert!(!args.to_json_spec().contains("hunter2"));
+    /// assert!(!args.generate_man_page().contains("hunter2"));
+    /// assert!(!args.to_markdown().contains("hunter2"));
+    /// assert!(args.to_markdown().contains("`***`"));
     /// ```
-    fn no_default_arguments(&mut self) {
-        for _ in 0..2 {
-            self.arguments.remove(0);
+    pub fn secret(mut self, secret: bool) -> Arg {
+        self.secret = secret;
+        self
+    }
+
+    /// Function used to mark the argument as required
+    pub fn required(mut self, required: bool) -> Arg {
+        self.required = required;
+        self
+    }
+
+    /// Function used to set a default parameter of the argument, used when the argument or its parameter
+    /// wasn't passed
+    pub fn default(mut self, value: &str) -> Arg {
+        self.default = Some(value.to_string());
+        self.takes_value = true;
+        self
+    }
+
+    /// Function used to restrict parameters of the argument to a set of allowed values
+    pub fn choices(mut self, choices: &[&str]) -> Arg {
+        self.choices = choices.iter().map(|c| c.to_string()).collect();
+        self.takes_value = true;
+        self
+    }
+
+    /// Function used to split parameters of the argument into multiple values on a delimiter (e.g. `--features a,b,c`)
+    pub fn delimiter(mut self, delimiter: char) -> Arg {
+        self.delimiter = Some(delimiter);
+        self.takes_value = true;
+        self
+    }
+
+    /// Function used to set the kind of parameter the argument takes, shown in the help screen
+    /// (`--output <FILE>`) and used by completion scripts
+    pub fn value_hint(mut self, hint: ValueHint) -> Arg {
+        self.value_hint = Some(hint);
+        self.takes_value = true;
+        self
+    }
+
+    /// Function used to set the name of the parameter the argument takes, shown in the help screen
+    /// (`--output <PATH>`) instead of the name derived from the argument or its value hint
+    pub fn value_name(mut self, name: &str) -> Arg {
+        self.value_name = Some(name.to_string());
+        self.takes_value = true;
+        self
+    }
+
+    /// Function used to set an environment variable used when the argument isn't passed
+    pub fn env(mut self, variable: &str) -> Arg {
+        self.env = Some(variable.to_string());
+        self.takes_value = true;
+        self
+    }
+
+    /// Function used to hide the argument from the help screen, completions and man page
+    pub fn hidden(mut self, hidden: bool) -> Arg {
+        self.hidden = hidden;
+        self
+    }
+
+    /// Function used to also register a `--no-` form of the argument (e.g. `--no-color` for `--color`)
+    pub fn negatable(mut self, negatable: bool) -> Arg {
+        self.negatable = negatable;
+        self
+    }
+
+    /// Function used to make the argument match every token starting with its name (e.g. `-Dkey=value` for `-D`)
+    pub fn prefix(mut self, prefix: bool) -> Arg {
+        self.prefix = prefix;
+        self
+    }
+
+    /// Function used to set the position of the argument in the help screen: arguments with lower keys
+    /// are listed first, and arguments without a key after all of them (see `Argpars::help_order()`)
+    pub fn display_order(mut self, order: usize) -> Arg {
+        self.display_order = Some(order);
+        self
+    }
+
+    /// Function used to put the argument under a group heading in the help screen
+    pub fn group(mut self, group: &str) -> Arg {
+        self.group = Some(group.to_string());
+        self
+    }
+
+    // Returns true if a token is the name or one of the aliases of the argument
+    fn matches(&self, token: &str) -> bool {
+        self.name == token || is_value_in_a_vector_str(token, &self.aliases)
+    }
+
+    // Returns true if the argument was declared as not taking a parameter
+    fn is_flag(&self) -> bool {
+        self.flag && !self.takes_value
+    }
+
+    // Returns the name of the parameter shown in the help screen (`PATH` in `--output <PATH>`):
+    // the value name, the name of the value hint or the argument name in capitals
+    pub(crate) fn placeholder(&self) -> Option<String> {
+        if !self.takes_value || self.prefix {
+            return None;
+        }
+        let placeholder: String = match (&self.value_name, self.value_hint) {
+            (Some(name), _) => name.clone(),
+            (None, Some(hint)) => hint.placeholder().to_string(),
+            (None, None) if self.key_value => "KEY=VALUE".to_string(),
+            (None, None) => self
+                .name
+                .trim_start_matches('-')
+                .to_uppercase()
+                .replace('-', "_"),
+        };
+        Some(placeholder)
+    }
+}
+
+/// Description of a single positional argument of the app
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Positional {
+    pub name: String,
+    pub description: String,
+    pub required: bool,
+    pub trailing: bool,
+}
+
+/// Summary of a registered argument, returned by `iter_args()`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArgInfo<'a> {
+    pub name: &'a str,
+    pub aliases: &'a [String],
+    pub description: &'a str,
+    pub takes_value: bool,
+    pub required: bool,
+    pub default: Option<&'a str>,
+    pub hidden: bool,
+}
+
+/// Snapshot of registered arguments, positional arguments and rules between them, returned by `definitions()`
+///
+/// Validators, handlers and extensions are shared with the app the snapshot was taken from
+#[cfg(feature = "std")]
+#[derive(Clone)]
+pub struct Definitions {
+    args: Vec<Arg>,
+    positionals: Vec<Positional>,
+    validators: Vec<(String, Validator)>,
+    handlers: Vec<(String, Handler)>,
+    extensions: Vec<Rc<dyn ArgparsExtension>>,
+    range_constraints: Vec<(String, RangeBoundaries, Validator)>,
+    exclusive_groups: Vec<Vec<String>>,
+    requirements: Vec<(String, String)>,
+    conditional_requirements: Vec<(String, String)>,
+    deprecated_aliases: Vec<(String, String)>,
+    help_topics: Vec<(String, String)>,
+    current_group: Option<String>,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Debug for Definitions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Definitions")
+            .field("args", &self.args)
+            .field("positionals", &self.positionals)
+            .field("exclusive_groups", &self.exclusive_groups)
+            .field("requirements", &self.requirements)
+            .field("conditional_requirements", &self.conditional_requirements)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Error returned when a parameter could not be converted into the requested type
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub argument: String,
+    pub value: String,
+    pub reason: String,
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Invalid value \'{}\' for \'{}\': {}",
+            self.value, self.argument, self.reason
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+/// Errors found by the parser
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArgparsError {
+    /// A passed token is not a registered argument (token)
+    UnknownOption(String),
+    /// A passed abbreviation matches more than one argument (token, matching arguments)
+    AmbiguousOption(String, Vec<String>),
+    /// An argument expecting a parameter was passed without one (argument)
+    MissingValue(String),
+    /// A required argument was not passed (argument)
+    MissingRequiredArgument(String),
+    /// A required positional argument was not passed (positional argument name)
+    MissingRequiredPositional(String),
+    /// A parameter was rejected by a validator or couldn't be converted (argument, parameter)
+    InvalidValue(String, String),
+    /// A parameter is not one of the allowed values (argument, parameter, allowed values)
+    InvalidChoice(String, String, Vec<String>),
+    /// A numeric parameter is outside of the accepted range (argument, parameter, accepted range)
+    OutOfRange(String, String, String),
+    /// Two mutually exclusive arguments were passed (argument, argument)
+    ConflictingOptions(String, String),
+    /// An argument was passed without another one it requires (argument, required argument)
+    MissingDependency(String, String),
+    /// The number of passed positional arguments is outside of the accepted range (number, accepted range)
+    WrongNumberOfPositionals(usize, String),
+    /// A passed value is neither a parameter of an argument nor bound to a positional argument (value)
+    UnexpectedValue(String),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for ArgparsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", Messages::default().error_message(self))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ArgparsError {}
+
+#[cfg(feature = "std")]
+// Lets errors of the standalone `parser::parse()` be reported like errors of `ArgsObj`
+impl From<parser::Error<'_>> for ArgparsError {
+    fn from(err: parser::Error) -> ArgparsError {
+        match err {
+            parser::Error::UnknownOption(option) => ArgparsError::UnknownOption(option.to_string()),
+            parser::Error::MissingValue(arg) => ArgparsError::MissingValue(arg.to_string()),
         }
-        for _ in 0..4 {
-            self.arg_desc_vec.remove(0);
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<ParseError> for ArgparsError {
+    fn from(err: ParseError) -> ArgparsError {
+        ArgparsError::InvalidValue(err.argument, err.value)
+    }
+}
+
+/// Exit codes returned by `pars()` for each class of errors
+///
+/// All of them are 1 by default
+///
+/// # Examples
+///
+/// ```no_run
+/// use argpars::*;
+///
+/// let mut args: ArgsObj = Argpars::new();
+/// // Following sysexits.h
+/// args.exit_codes(ExitCodes { usage_error: 64, missing_required: 64, validation_failure: 65 });
+/// ```
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExitCodes {
+    /// Unknown options, missing parameters, conflicting options, missing dependencies and a wrong number
+    /// of positional arguments
+    pub usage_error: i32,
+    /// Missing required arguments and positional arguments
+    pub missing_required: i32,
+    /// Parameters rejected by validators, outside of allowed values or which couldn't be converted
+    pub validation_failure: i32,
+}
+
+#[cfg(feature = "std")]
+impl Default for ExitCodes {
+    fn default() -> ExitCodes {
+        ExitCodes {
+            usage_error: 1,
+            missing_required: 1,
+            validation_failure: 1,
         }
-        self.passed_arguments_lookup.remove_entry("--help");
-        self.passed_arguments_lookup.remove_entry("--version");
-        self.parameters_lookup.remove_entry("--help");
-        self.parameters_lookup.remove_entry("--version");
-        self.default_arguments = false;
     }
+}
 
-    /// Function returning if no arguments were passed
+#[cfg(feature = "std")]
+impl ExitCodes {
+    /// Function returning the exit code for a given error
+    pub fn code_for(&self, err: &ArgparsError) -> i32 {
+        match err {
+            ArgparsError::UnknownOption(_)
+            | ArgparsError::AmbiguousOption(_, _)
+            | ArgparsError::MissingValue(_)
+            | ArgparsError::ConflictingOptions(_, _)
+            | ArgparsError::MissingDependency(_, _)
+            | ArgparsError::WrongNumberOfPositionals(_, _)
+            | ArgparsError::UnexpectedValue(_) => self.usage_error,
+            ArgparsError::MissingRequiredArgument(_)
+            | ArgparsError::MissingRequiredPositional(_) => self.missing_required,
+            ArgparsError::InvalidValue(_, _)
+            | ArgparsError::InvalidChoice(_, _, _)
+            | ArgparsError::OutOfRange(_, _, _) => self.validation_failure,
+        }
+    }
+}
+
+/// Order of arguments in the help screen
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HelpOrder {
+    /// Order in which arguments were added (the default)
+    #[default]
+    Declaration,
+    /// Alphabetical order of argument names, ignoring leading dashes and case
+    Alphabetical,
+}
+
+/// Format of error messages displayed by `pars()`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorFormat {
+    /// Error message followed by the usage line (the default)
+    #[default]
+    Text,
+    /// Single-line JSON object with the kind of the error, the wrong token, its position,
+    /// a suggestion and the error message
+    Json,
+}
+
+/// Where the parameter of an argument comes from, returned by `resolved_value()`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueSource {
+    /// Passed on the command line
+    CommandLine,
+    /// Read from the environment variable of the argument
+    Environment,
+    /// Read from the config file
+    ConfigFile,
+    /// Default parameter of the argument
+    Default,
+}
+
+/// Arguments, parameters and positional arguments found by `try_pars()`
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedArgs {
+    pub arguments: Vec<String>,
+    pub parameters: HashMap<String, Vec<String>>,
+    pub positionals: HashMap<String, String>,
+    pub trailing_arguments: Vec<String>,
+    pub unknown_arguments: Vec<String>,
+    pub raw_arguments: Vec<String>,
+    pub indices: HashMap<String, Vec<usize>>,
+    aliases: HashMap<String, String>,
+    secrets: Vec<String>,
+}
+
+#[cfg(feature = "std")]
+impl ParsedArgs {
+    // Returns the long form of an argument if a short alias was given, otherwise the argument itself
+    fn canonical_argument<'a>(&'a self, arg: &'a str) -> &'a str {
+        match self.aliases.get(arg) {
+            Some(long) => long,
+            None => arg,
+        }
+    }
+
+    /// Function which checks if an argument was passed
+    pub fn passed(&self, arg: &str) -> bool {
+        is_value_in_a_vector_str(self.canonical_argument(arg), &self.arguments)
+    }
+
+    /// Function used to retrive passed parameter to an argument (empty if there is none)
+    pub fn get_parameter_for(&self, arg: &str) -> &str {
+        self.get_parameters_for(arg).first().copied().unwrap_or("")
+    }
+
+    /// Function used to retrive all passed parameters to an argument
+    pub fn get_parameters_for(&self, arg: &str) -> Vec<&str> {
+        match self.parameters.get(self.canonical_argument(arg)) {
+            Some(parameters) => parameters.iter().map(|p| p.as_str()).collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Function used to retrive parameters of an argument taking `KEY=VALUE` parameters as a map
+    pub fn get_map_for(&self, arg: &str) -> HashMap<String, String> {
+        self.get_parameters_for(arg)
+            .into_iter()
+            .filter_map(|parameter| parameter.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    /// Function used to retrive the value bound to a positional argument (empty if it wasn't passed)
+    pub fn get_positional(&self, name: &str) -> &str {
+        match self.positionals.get(name) {
+            Some(value) => value,
+            None => "",
+        }
+    }
+
+    /// Function which checks if the parameter of an argument, or the value of a positional argument, is `-` (stdin)
+    pub fn value_is_stdin(&self, arg: &str) -> bool {
+        match self.positionals.get(arg) {
+            Some(value) => value == "-",
+            None => self.get_parameter_for(arg) == "-",
+        }
+    }
+
+    /// Function used to retrive arguments passed after the `--` separator
+    pub fn trailing_args(&self) -> &[String] {
+        &self.trailing_arguments
+    }
+
+    /// Function used to retrive passed arguments which are not registered (see `allow_unknown()`)
+    pub fn unknown_args(&self) -> &[String] {
+        &self.unknown_arguments
+    }
+
+    /// Function returning a bitmask of passed flags: bit `i` is set if `flags[i]` was passed
+    /// (see `Argpars::flags_set()`)
+    pub fn flags_set(&self, flags: &[&str]) -> u32 {
+        flags
+            .iter()
+            .take(32)
+            .enumerate()
+            .filter(|(_, flag)| self.passed(flag))
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    /// Function which checks if an argument was passed (same as `passed()`)
+    pub fn is_present(&self, arg: &str) -> bool {
+        self.passed(arg)
+    }
+
+    /// Function used to retrive the first parameter of an argument, if there is one
+    pub fn value_of(&self, arg: &str) -> Option<&str> {
+        self.get_parameters_for(arg).first().copied()
+    }
+
+    /// Function used to retrive all parameters of an argument (same as `get_parameters_for()`)
+    pub fn values_of(&self, arg: &str) -> Vec<&str> {
+        self.get_parameters_for(arg)
+    }
+
+    /// Function used to retrive the value bound to a positional argument, if it was passed
+    pub fn positional(&self, name: &str) -> Option<&str> {
+        self.positionals.get(name).map(|value| value.as_str())
+    }
+
+    /// Function used to retrive the position of the first occurrence of an argument or of a positional argument's value
+    /// (see `Argpars::index_of()`)
+    pub fn index_of(&self, arg: &str) -> Option<usize> {
+        self.indices_of(arg).first().copied()
+    }
+
+    /// Function used to retrive positions of every occurrence of an argument, in order
+    pub fn indices_of(&self, arg: &str) -> Vec<usize> {
+        match self.indices.get(self.canonical_argument(arg)) {
+            Some(indices) => indices.clone(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Function used to retrive the arguments passed to the app, as they were passed
+    pub fn raw_args(&self) -> &[String] {
+        &self.raw_arguments
+    }
+
+    /// Function used to export passed arguments (with their parameters, empty for flags, `***` for secret
+    /// arguments), positional arguments (with their value) and arguments passed after the `--` separator
+    /// (under `--`) in a single map
+    pub fn to_map(&self) -> HashMap<String, Vec<String>> {
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for arg in &self.arguments {
+            let mut parameters: Vec<String> = self.parameters.get(arg).cloned().unwrap_or_default();
+            if self.secrets.contains(arg) {
+                parameters.fill(REDACTED.to_string());
+            }
+            map.insert(arg.clone(), parameters);
+        }
+        for (name, value) in &self.positionals {
+            map.insert(name.clone(), vec![value.clone()]);
+        }
+        if !self.trailing_arguments.is_empty() {
+            map.insert("--".to_string(), self.trailing_arguments.clone());
+        }
+        map
+    }
+}
+
+/// Arguments found by `parse()`, independent from the `ArgsObj` they were parsed with
+#[cfg(feature = "std")]
+pub type Matches = ParsedArgs;
+
+/// Definitions of arguments used by `parse_tokens()`
+#[cfg(feature = "std")]
+pub type ArgDefs = ArgsObj;
+
+/// Implementation of Argpars for the ArgsObj struct
+#[cfg(feature = "std")]
+impl Argpars for ArgsObj {
+    /// ArgsObj constructor
+    ///
+    /// Arguments which are not valid UTF-8 don't cause a panic (see `from_os_args()`)
     ///
     /// # Examples
     ///
@@ -173,124 +1117,280 @@ impl Argpars for ArgsObj {
     /// use argpars::*;
     ///
     /// let mut args: ArgsObj = Argpars::new();
-    /// if args.no_arguments_passed() {
-    ///     println!("no arguments passed");
-    /// }
     /// ```
-    fn no_arguments_passed(&self) -> bool {
-        self.number_of_arguments == 1
+    fn new() -> ArgsObj {
+        Argpars::from_os_args()
     }
 
-    /// Function which checks if an arguments was passed
+    /// ArgsObj constructor keeping the process arguments as `OsString`s
+    ///
+    /// Arguments are parsed after a lossy UTF-8 conversion, while the original values of parameters
+    /// (e.g. file names which are not valid UTF-8) can be retrived with `get_parameter_os_for()`
+    ///
+    /// When the `POSIXLY_CORRECT` environment variable is set, `posix_mode()` is enabled
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use argpars::*;
     ///
-    /// let mut args: ArgsObj = Argpars::new();
-    /// if args.passed("--test") {
-    ///     println!("--test passed");
-    /// }
+    /// let mut args: ArgsObj = Argpars::from_os_args();
     /// ```
-    fn passed(&self, arg: &str) -> bool {
-        is_value_in_a_vector_str(arg, &self.arguments_passed)
+    fn from_os_args() -> ArgsObj {
+        let mut args: ArgsObj = Argpars::from_os_vec(std::env::args_os().collect());
+        if std::env::var_os("POSIXLY_CORRECT").is_some() {
+            args.posix_mode(true);
+        }
+        args
     }
 
-    /// Function used to add an argument into the app
+    /// ArgsObj constructor parsing the given `OsString` argument vector
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use argpars::*;
+    /// use std::ffi::OsString;
     ///
-    /// let mut args: ArgsObj = Argpars::new();
-    /// args.add_argument("--test", "test argument");
+    /// let args: ArgsObj = Argpars::from_os_vec(vec![OsString::from("app"), OsString::from("--help")]);
     /// ```
-    fn add_argument(&mut self, argument: &str, description: &str) {
-        self.arguments.push(argument.to_string());
-        self.arg_desc_vec.push(argument.to_string());
-        self.arg_desc_vec.push(description.to_string());
-        self.passed_arguments_lookup
-            .insert(argument.to_string(), false);
-        self.parameters_lookup
-            .insert(argument.to_string(), "".to_string());
-        self.lookup_update();
+    fn from_os_vec(arguments: Vec<OsString>) -> ArgsObj {
+        let mut args: ArgsObj = Argpars::from_vec(
+            arguments
+                .iter()
+                .map(|a| a.to_string_lossy().to_string())
+                .collect(),
+        );
+        args.arguments_passed_os = arguments;
+        args
     }
 
-    /// Function returning if default arguments were passed
+    /// ArgsObj constructor parsing a command line (split with `split()`) instead of the process arguments
+    ///
+    /// The first word is treated as the program name, which is useful for commands of interactive shells
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use argpars::*;
     ///
-    /// let mut args: ArgsObj = Argpars::new();
-    /// if args.default_arguments_passed() {
-    ///     println!("default arguments passed")
-    /// }
+    /// let mut args: ArgsObj = Argpars::from_line("open \"my notes.txt\" --read-only");
+    /// args.add_argument("--read-only", "open the file in read-only mode");
+    /// args.add_positional("FILE", "file to open");
     /// ```
-    fn default_arguments_passed(&self) -> bool {
-        self.passed("--help") || self.passed("--version")
+    fn from_line(line: &str) -> ArgsObj {
+        Argpars::from_vec(split(line))
     }
 
-    /// Function returning if wrong (non existent) arguments / parameters were passed
+    /// ArgsObj constructor adding arguments described by `Arg`s (e.g. generated from a schema) at once
+    ///
+    /// # Panics
+    ///
+    /// Panics if an argument name or alias is registered more than once (see `add_arg()`)
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use argpars::*;
     ///
-    /// let mut args: ArgsObj = Argpars::new();
-    /// if args.wrong_arguments_passed() {
-    ///     println!("wrong arguments passed")
-    /// }
+    /// let specs: Vec<Arg> = vec![
+    ///     Arg::new("--host", "host to connect to").default("localhost"),
+    ///     Arg::new("--port", "port to connect to").alias("-p").takes_value(true),
+    ///     Arg::new("--verbose", "explain what is being done").takes_value(false),
+    /// ];
+    /// let args: ArgsObj = Argpars::from_spec(&specs);
     /// ```
-    fn wrong_arguments_passed(&self) -> bool {
-        let mut loop_end: usize = self.number_of_arguments as usize;
-        if self.last_param_ok {
-            loop_end -= 1;
-        }
-        for i in 1..loop_end {
-            if self.arguments_passed[i as usize].starts_with('-') {
-                if !self.arguments.contains(&self.arguments_passed[i as usize]) {
-                    return true;
-                }
-            } else if !self
-                .arguments
-                .contains(&self.arguments_passed[(i - 1) as usize])
-            {
-                return true;
-            }
-        }
-        false
+    fn from_spec(specs: &[Arg]) -> ArgsObj {
+        let mut args: ArgsObj = Argpars::new();
+        args.add_args(specs);
+        args
     }
 
-    /// Function used to retrive passed parameter to an argument
+    /// ArgsObj constructor parsing the given argument vector instead of the process arguments
+    ///
+    /// The first element is treated as the program name, just like in `std::env::args()`
     ///
     /// # Examples
     ///
     /// ```no_run
     /// use argpars::*;
     ///
-    /// let mut args: ArgsObj = Argpars::new();
-    /// println!("parameter for --help: {}", args.get_parameter_for("--help"));
+    /// let args: ArgsObj = Argpars::from_vec(vec!["app".to_string(), "--help".to_string()]);
     /// ```
-    fn get_parameter_for(&self, arg: &str) -> &str {
-        let index_of_argument: usize = self.arguments_passed.iter().position(|r| r == arg).unwrap();
-        let index_of_parameter: usize = index_of_argument + 1;
-        if index_of_parameter < self.arguments_passed.len()
-            && !self
-                .arguments
-                .contains(&self.arguments_passed[index_of_parameter])
-        {
-            return &self.arguments_passed[index_of_parameter];
+    fn from_vec(arguments: Vec<String>) -> ArgsObj {
+        let mut args: ArgsObj = ArgsObj {
+            number_of_arguments: arguments.len() as u32,
+            arguments_expanded: Vec::new(),
+            trailing_arguments: Vec::new(),
+            arguments_passed_os: arguments.iter().map(OsString::from).collect(),
+            arguments_passed: arguments,
+            args: vec![
+                Arg::new("--help", "display this help and exit"),
+                Arg::new("--version", "output version information and exit").alias("-V"),
+            ],
+            default_arguments: true,
+            disabled_default_arguments: Vec::new(),
+            help_usage: String::new(),
+            help_name: "Default name".to_string(),
+            help_description: "Default description".to_string(),
+            help_version: "Default version".to_string(),
+            help_sections: Vec::new(),
+            help_sections_content: Vec::new(),
+            help_template: None,
+            before_help: String::new(),
+            after_help: String::new(),
+            version_template: None,
+            help_topics: Vec::new(),
+            current_group: None,
+            passed_arguments_lookup: HashMap::from([
+                ("--help".to_string(), false),
+                ("--version".to_string(), false),
+            ]),
+            parameters_lookup: HashMap::from([
+                ("--help".to_string(), "".to_string()),
+                ("--version".to_string(), "".to_string()),
+            ]),
+            parameters_list_lookup: HashMap::new(),
+            environment_lookup: HashMap::new(),
+            config_values: HashMap::new(),
+            validators: Vec::new(),
+            handlers: Vec::new(),
+            extensions: Vec::new(),
+            range_constraints: Vec::new(),
+            exclusive_groups: Vec::new(),
+            requirements: Vec::new(),
+            conditional_requirements: Vec::new(),
+            deprecated_aliases: Vec::new(),
+            positionals: Vec::new(),
+            color: ColorChoice::Auto,
+            error_format: ErrorFormat::Text,
+            exit_codes: ExitCodes::default(),
+            allow_unknown: false,
+            allow_abbreviations: false,
+            ignore_case: false,
+            windows_options: false,
+            help_command: false,
+            strict_values: false,
+            posix_mode: false,
+            trace: false,
+            paginate_help: true,
+            help_order: HelpOrder::Declaration,
+            default_arguments_last: false,
+            positionals_range: (Bound::Unbounded, Bound::Unbounded),
+            dynamic_completions: false,
+            messages: Messages::default(),
+            #[allow(deprecated)]
+            last_param_ok: false,
+            output: None,
+            error_output: None,
+            argument_names: HashMap::new(),
+            occurrences: HashMap::new(),
+            options_end: 0,
+            preceding_options: Vec::new(),
+            compound_tokens: Vec::new(),
+        };
+        args.lookup_update();
+        args
+    }
+
+    /// Function which updates lookup HashMaps such as passed_arguments_lookup, parameters_lookup
+    /// or environment_lookup
+    ///
+    /// parameters_lookup holds the parameter of the first occurrence of every passed argument, while
+    /// parameters_list_lookup holds parameters of all its occurrences (`-I dir1 -I dir2`)
+    ///
+    /// It also refreshes arguments_expanded, which holds the passed arguments (up to the `--` separator)
+    /// with bundled short flags and `--key=value` pairs split up, and trailing_arguments, which holds
+    /// the arguments passed after the `--` separator
+    ///
+    /// It has to be called after changing args or arguments_passed directly, as passed arguments are looked up
+    /// in indexes built here
+    fn lookup_update(&mut self) {
+        self.index_argument_names();
+        self.refresh_lookups();
+    }
+
+    /// Function which, when called, disables default arguments (--help, --version, ...)
+    ///
+    /// It can be called at any time, any number of times
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.no_default_arguments();
+    /// ```
+    fn no_default_arguments(&mut self) {
+        self.disable_default("--help");
+        self.disable_default("--version");
+    }
+
+    /// Function used to disable a single default argument (`--help` or `--version`), so that
+    /// it's neither handled by `pars()` nor shown in the help screen
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.disable_default("--version");
+    /// ```
+    fn disable_default(&mut self, argument: &str) {
+        let name: &str = match argument {
+            "--help" => "--help",
+            "--version" | "-V" => "--version",
+            _ => return,
+        };
+        if self.disabled_default_arguments.iter().any(|a| a == name) {
+            return;
         }
+        self.args.retain(|arg| arg.name != name);
+        self.passed_arguments_lookup.remove_entry(name);
+        self.parameters_lookup.remove_entry(name);
+        self.parameters_list_lookup.remove_entry(name);
+        self.disabled_default_arguments.push(name.to_string());
+        if self.disabled_default_arguments.len() == 2 {
+            self.default_arguments = false;
+        }
+        self.lookup_update();
+    }
+
+    /// Function returning if no arguments were passed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// if args.no_arguments_passed() {
+    ///     println!("no arguments passed");
+    /// }
+    /// ```
+    fn no_arguments_passed(&self) -> bool {
+        self.number_of_arguments <= 1
+    }
 
-        ""
+    /// Function which checks if an arguments was passed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// if args.passed("--test") {
+    ///     println!("--test passed");
+    /// }
+    /// ```
+    fn passed(&self, arg: &str) -> bool {
+        self.occurrences.contains_key(self.canonical_argument(arg))
     }
 
-    /// Function used to display error messages
+    /// Function returning how many times an argument was passed, including bundled short forms (`-vvv`)
     ///
     /// # Examples
     ///
@@ -298,19 +1398,83 @@ impl Argpars for ArgsObj {
     /// use argpars::*;
     ///
     /// let mut args: ArgsObj = Argpars::new();
-    /// args.display_error_message("no_such_option", "additional");
+    /// args.add_argument_with_short("-v", "--verbose", "increase verbosity");
+    /// println!("verbosity level: {}", args.count_of("--verbose"));
     /// ```
-    fn display_error_message(&self, err_type: &str, additional: &str) {
-        if err_type == "no_such_option" {
-            eprintln!("ERROR: No such option: \'{}\'", additional);
-            eprintln!(
-                "Try: \'{} --help\' for more information.",
-                self.arguments_passed[0]
-            );
+    fn count_of(&self, arg: &str) -> usize {
+        self.positions_of(arg).len()
+    }
+
+    /// Function returning the verbosity level set by `standard_verbosity_flags()`:
+    /// the number of `-v`/`--verbose` minus the number of `-q`/`--quiet` passed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.standard_verbosity_flags();
+    /// args.pars();
+    /// if args.verbosity() > 1 {
+    ///     println!("debug output enabled");
+    /// }
+    /// ```
+    fn verbosity(&self) -> i32 {
+        self.count_of("--verbose") as i32 - self.count_of("--quiet") as i32
+    }
+
+    /// Function returning the state of a flag set by its last occurrence: `Some(true)` for the flag,
+    /// `Some(false)` for its `--no-` form (see `Arg::negatable()`) and `None` if neither was passed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_arg(Arg::new("--color", "colorize the output").negatable(true));
+    /// let color: bool = args.flag_state("--color").unwrap_or(true);
+    /// ```
+    fn flag_state(&self, arg: &str) -> Option<bool> {
+        let canonical: &str = self.canonical_argument(arg);
+        let negation: String = format!("--no-{}", canonical.trim_start_matches('-'));
+        match (
+            self.positions_of(canonical).last(),
+            self.positions_of(&negation).last(),
+        ) {
+            (None, None) => None,
+            (enabled, disabled) => Some(enabled > disabled),
         }
     }
 
-    /// Function used to display the help screen
+    /// Function returning a bitmask of passed flags: bit `i` is set if `flags[i]` was passed
+    /// (and not turned off by its `--no-` form), only the first 32 flags are taken into account
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --read --exec"));
+    /// args.add_flag("--read", "allow reading");
+    /// args.add_flag("--write", "allow writing");
+    /// args.add_flag("--exec", "allow executing");
+    /// assert_eq!(args.flags_set(&["--read", "--write", "--exec"]), 0b101);
+    /// ```
+    fn flags_set(&self, flags: &[&str]) -> u32 {
+        flags
+            .iter()
+            .take(32)
+            .enumerate()
+            .filter(|(_, flag)| self.flag_state(flag) == Some(true))
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    }
+
+    /// Function returning positions of every occurrence of an argument (or its aliases), in order
+    ///
+    /// Positions are indexes into arguments_expanded, where 0 is the program name
+    /// and `--key=value` pairs and bundled short flags are already split up
     ///
     /// # Examples
     ///
@@ -318,41 +1482,105 @@ impl Argpars for ArgsObj {
     /// use argpars::*;
     ///
     /// let mut args: ArgsObj = Argpars::new();
-    /// args.display_help_screen();
+    /// args.add_argument("--quiet", "print nothing");
+    /// args.add_argument("--verbose", "print everything");
+    /// // The last passed of the two wins
+    /// let quiet: bool = args.occurrences_of("--quiet").last() > args.occurrences_of("--verbose").last();
     /// ```
-    fn display_help_screen(&self) {
-        println!("{}", self.help_usage);
-        println!("Name: {}", self.help_name);
-        println!("Description: {}", self.help_description);
-        println!("Version: {}\n", self.help_version);
-        println!("Possible options:");
-        for arg in &self.arguments {
-            if self.arg_desc_vec.contains(arg) {
-                let desc_index: usize =
-                    self.arg_desc_vec.iter().position(|a| a == arg).unwrap() + 1;
-                println!("\t{}\t{}", arg, self.arg_desc_vec[desc_index]);
-            } else {
-                println!("\t{}", arg);
-            }
+    fn occurrences_of(&self, arg: &str) -> Vec<usize> {
+        self.positions_of(arg).to_vec()
+    }
+
+    /// Function returning the position of the first occurrence of an argument (or its aliases),
+    /// or of the value bound to a positional argument
+    ///
+    /// Positions are counted like in `occurrences_of()`; values passed after the `--` separator
+    /// come after arguments_expanded and the separator itself
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--name", "match files by name");
+    /// args.add_argument("--newer", "match files newer than a given file");
+    /// args.pars();
+    /// if args.index_of("--name") < args.index_of("--newer") {
+    ///     println!("filtering by name first");
+    /// }
+    /// ```
+    fn index_of(&self, arg: &str) -> Option<usize> {
+        if let Some(index) = self.occurrences_of(arg).first() {
+            return Some(*index);
         }
-        if !self.help_sections.is_empty() {
-            println!();
-            for section in &self.help_sections {
-                println!("{}", section);
-                if self.help_sections_content.contains(section) {
-                    let content_index: usize = self
-                        .help_sections_content
-                        .iter()
-                        .position(|a| a == section)
-                        .unwrap()
-                        + 1;
-                    println!("{}", self.help_sections_content[content_index]);
-                }
-            }
+        self.bound_positionals()
+            .into_iter()
+            .find(|(positional, _)| positional.name == arg)
+            .map(|(_, index)| index)
+    }
+
+    /// Function returning a summary of every registered argument (including default and hidden ones), in order
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument_with_short("-v", "--verbose", "explain what is being done");
+    /// for arg in args.iter_args().filter(|arg| !arg.hidden) {
+    ///     println!("{} {:?}: {}", arg.name, arg.aliases, arg.description);
+    /// }
+    /// ```
+    fn iter_args(&self) -> impl Iterator<Item = ArgInfo<'_>> {
+        self.args.iter().map(|arg| ArgInfo {
+            name: &arg.name,
+            aliases: &arg.aliases,
+            description: arg.description.trim(),
+            takes_value: arg.takes_value,
+            required: arg.required,
+            default: arg.default.as_deref(),
+            hidden: arg.hidden,
+        })
+    }
+
+    /// Function returning a snapshot of registered arguments, positional arguments and rules between them,
+    /// which can be restored with `reset_to()`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--output", "file to write to");
+    /// let base: Definitions = args.definitions();
+    /// args.add_flag("--plugin-verbose", "added by a plugin");
+    /// args.reset_to(base);
+    /// assert!(args.iter_args().all(|arg| arg.name != "--plugin-verbose"));
+    /// ```
+    fn definitions(&self) -> Definitions {
+        Definitions {
+            args: self.args.clone(),
+            positionals: self.positionals.clone(),
+            validators: self.validators.clone(),
+            handlers: self.handlers.clone(),
+            extensions: self.extensions.clone(),
+            range_constraints: self.range_constraints.clone(),
+            exclusive_groups: self.exclusive_groups.clone(),
+            requirements: self.requirements.clone(),
+            conditional_requirements: self.conditional_requirements.clone(),
+            deprecated_aliases: self.deprecated_aliases.clone(),
+            help_topics: self.help_topics.clone(),
+            current_group: self.current_group.clone(),
         }
     }
 
-    /// Function used to add a section into the help screen
+    /// Function used to replace registered arguments, positional arguments and rules between them
+    /// with a snapshot taken by `definitions()`
+    ///
+    /// Passed arguments and other settings of the app are kept
     ///
     /// # Examples
     ///
@@ -360,15 +1588,33 @@ impl Argpars for ArgsObj {
     /// use argpars::*;
     ///
     /// let mut args: ArgsObj = Argpars::new();
-    /// args.add_help_section("TEST SECTION:", "\tthis is a test section!\n");
+    /// let base: Definitions = args.definitions();
+    /// args.add_argument("--plugin-option", "added by a plugin");
+    /// args.pars();
+    /// args.reset_to(base);
     /// ```
-    fn add_help_section(&mut self, section: &str, content: &str) {
-        self.help_sections.push(section.to_string());
-        self.help_sections_content.push(section.to_string());
-        self.help_sections_content.push(content.to_string());
+    fn reset_to(&mut self, definitions: Definitions) {
+        self.args = definitions.args;
+        self.positionals = definitions.positionals;
+        self.validators = definitions.validators;
+        self.handlers = definitions.handlers;
+        self.extensions = definitions.extensions;
+        self.range_constraints = definitions.range_constraints;
+        self.exclusive_groups = definitions.exclusive_groups;
+        self.requirements = definitions.requirements;
+        self.conditional_requirements = definitions.conditional_requirements;
+        self.deprecated_aliases = definitions.deprecated_aliases;
+        self.help_topics = definitions.help_topics;
+        self.current_group = definitions.current_group;
+        self.lookup_update();
     }
 
-    /// Main Argpars parser
+    /// Function used to add an argument described by an `Arg` into the app
+    ///
+    /// # Panics
+    ///
+    /// Panics if the name or one of the aliases of the argument is already registered
+    /// (except for `-V`, which is taken over from `--version`)
     ///
     /// # Examples
     ///
@@ -376,50 +1622,3799 @@ impl Argpars for ArgsObj {
     /// use argpars::*;
     ///
     /// let mut args: ArgsObj = Argpars::new();
-    /// // Executing Argpars parser and exiting from the app with a return value
-    /// std::process::exit(args.pars());
+    /// args.add_arg(Arg::new("--port", "port to listen on").alias("-p").default("8080"));
     /// ```
-    fn pars(&self) -> i32 {
-        if self.no_arguments_passed() {
-            // // Displaying help screen if no arguments were passed (disabled by default):
-            // self.display_help_screen();
-        } else {
-            let mut loop_end: usize = self.number_of_arguments as usize;
-            if self.last_param_ok {
-                loop_end -= 1;
-            }
-            for i in 1..loop_end {
-                // If there is a '-' character at the beginning and it is not an known argument, throw an error
-                if self.arguments_passed[i as usize].starts_with('-') {
-                    if !self.arguments.contains(&self.arguments_passed[i as usize]) {
-                        self.display_error_message(
-                            "no_such_option",
-                            &self.arguments_passed[i as usize],
-                        );
-                        return 1;
-                    }
-                }
-                // If there is no '-' character at the beginning and the previous argument is now a known one, throw an error
-                else if !is_value_in_a_vector_str(
-                    &self.arguments_passed[(i - 1) as usize],
-                    &self.arguments,
-                ) {
-                    self.display_error_message(
-                        "no_such_option",
-                        &self.arguments_passed[i as usize],
-                    );
-                    return 1;
-                }
-            }
-            if self.default_arguments {
-                if self.passed("--help") {
-                    self.display_help_screen();
-                }
-                if self.passed("--version") {
-                    println!("{} version: {}", self.help_name, self.help_version);
-                }
+    fn add_arg(&mut self, arg: Arg) {
+        let start: usize = self.args.len();
+        self.insert_arg(arg);
+        self.lookup_insert(start);
+    }
+
+    /// Function used to add arguments described by `Arg`s at once, faster than adding them one by one
+    ///
+    /// # Panics
+    ///
+    /// Panics if an argument name or alias is registered more than once (see `add_arg()`)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --jobs 4"));
+    /// let specs: Vec<Arg> = (1..=100)
+    ///     .map(|i| Arg::new(&format!("--option-{}", i), "generated option"))
+    ///     .chain(std::iter::once(Arg::new("--jobs", "number of jobs").takes_value(true)))
+    ///     .collect();
+    /// args.add_args(&specs);
+    /// assert_eq!(args.get_parameter_for("--jobs"), "4");
+    /// ```
+    fn add_args(&mut self, specs: &[Arg]) {
+        for arg in specs {
+            self.insert_arg(arg.clone());
+        }
+        self.refresh_lookups();
+    }
+
+    /// Function used to add an argument into the app
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--test", "test argument");
+    /// ```
+    fn add_argument(&mut self, argument: &str, description: &str) {
+        self.add_arg(Arg::new(argument, description));
+    }
+
+    /// Function which checks, in debug builds, that the definition of the app is consistent: that there are
+    /// no duplicate names or aliases and that relations, defaults and positional arguments make sense
+    ///
+    /// # Panics
+    ///
+    /// Panics with a list of found problems (only in debug builds)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--json", "output JSON");
+    /// args.conflicts_with("--json", "--yaml");
+    /// // Panics, as --yaml is not registered
+    /// args.debug_assert_valid();
+    /// ```
+    fn debug_assert_valid(&self) {
+        if cfg!(debug_assertions) {
+            let problems: Vec<String> = self.definition_problems();
+            if !problems.is_empty() {
+                panic!("argpars: invalid definition:\n  {}", problems.join("\n  "));
             }
         }
-        0
+    }
+
+    /// Function used to add an argument with a short alias (e.g. `-v` for `--verbose`) into the app
+    ///
+    /// Both forms are recognized by `passed()`, `get_parameter_for()` and shown on the help screen
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument_with_short("-v", "--verbose", "explain what is being done");
+    /// if args.passed("--verbose") {
+    ///     println!("-v or --verbose passed");
+    /// }
+    /// ```
+    fn add_argument_with_short(&mut self, short: &str, argument: &str, description: &str) {
+        self.add_arg(Arg::new(argument, description).alias(short));
+    }
+
+    /// Function used to add an argument which never takes a parameter into the app
+    ///
+    /// A value following it is treated as a positional argument, and `--flag=value` is an error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_flag("--force", "overwrite existing files");
+    /// args.add_positional("FILE", "file to write");
+    /// // `--force out.txt` binds out.txt to FILE
+    /// ```
+    fn add_flag(&mut self, argument: &str, description: &str) {
+        self.add_arg(Arg::new(argument, description).takes_value(false));
+    }
+
+    /// Function used to add the standard `-v`/`--verbose` (can be repeated) and `-q`/`--quiet` arguments into the app
+    ///
+    /// The resulting level is returned by `verbosity()`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.standard_verbosity_flags();
+    /// ```
+    fn standard_verbosity_flags(&mut self) {
+        self.add_arg(
+            Arg::new("--verbose", "increase verbosity (can be repeated)")
+                .alias("-v")
+                .takes_value(false),
+        );
+        self.add_arg(
+            Arg::new("--quiet", "decrease verbosity (can be repeated)")
+                .alias("-q")
+                .takes_value(false),
+        );
+    }
+
+    /// Function used to add an argument which has to be passed for the app to run
+    ///
+    /// If the argument is missing (also when no arguments were passed at all), `pars()` displays an error
+    /// and returns a nonzero value
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_required_argument("--input", "file to read from");
+    /// ```
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(vec!["app".to_string()]);
+    /// args.add_required_argument("--input", "file to read from");
+    /// args.add_required_positional("OUTPUT", "file to write to");
+    /// assert_eq!(args.pars(), 1);
+    /// ```
+    fn add_required_argument(&mut self, argument: &str, description: &str) {
+        self.add_arg(Arg::new(argument, description).required(true));
+    }
+
+    /// Function used to add an argument which is parsed but never shown in the help screen or completions
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_hidden_argument("--internal-debug", "print internal state");
+    /// ```
+    fn add_hidden_argument(&mut self, argument: &str, description: &str) {
+        self.add_arg(Arg::new(argument, description).hidden(true));
+    }
+
+    /// Function used to add an argument matching every token starting with a prefix (e.g. `-Dkey=value` for `-D`)
+    ///
+    /// Values following the prefix are returned by `prefix_values()`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_prefix_argument("-D", "define a property");
+    /// ```
+    fn add_prefix_argument(&mut self, prefix: &str, description: &str) {
+        self.add_arg(Arg::new(prefix, description).prefix(true));
+    }
+
+    /// Function used to start a group of arguments, printed under its own heading in the help screen
+    ///
+    /// All arguments added afterwards (unless given another group with `Arg::group()`) belong to the group
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// args.add_group("Network options");
+    /// args.add_argument("--host", "host to connect to");
+    /// args.add_argument("--port", "port to connect to");
+    /// ```
+    fn add_group(&mut self, group: &str) {
+        self.current_group = Some(group.to_string());
+    }
+
+    /// Function used to add an argument accepting multiple parameters (e.g. `--file a.txt b.txt`)
+    ///
+    /// All values following the argument, up to the next argument, are its parameters
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_multi_value_argument("--file", "files to process");
+    /// ```
+    fn add_multi_value_argument(&mut self, argument: &str, description: &str) {
+        self.add_arg(Arg::new(argument, description).multi_value(true));
+    }
+
+    /// Function used to add an argument taking `KEY=VALUE` parameters, which can be passed multiple times
+    /// (e.g. `--env HOME=/root --env TERM=xterm`)
+    ///
+    /// Parameters are collected by `get_map_for()`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_map_argument("--env", "set an environment variable");
+    /// ```
+    fn add_map_argument(&mut self, argument: &str, description: &str) {
+        self.add_arg(Arg::new(argument, description).key_value(true));
+    }
+
+    /// Function used to set a default parameter of an argument
+    ///
+    /// The default is returned by `get_parameter_for()` when the argument or its parameter wasn't passed,
+    /// and is shown on the help screen next to the argument description
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--port", "port to listen on");
+    /// args.set_default("--port", "8080");
+    /// ```
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --port"));
+    /// args.add_argument("--port", "port to listen on");
+    /// args.set_default("--port", "8080");
+    /// assert!(args.try_pars().is_ok());
+    /// assert_eq!(args.get_parameter_for("--port"), "8080");
+    /// ```
+    fn set_default(&mut self, argument: &str, value: &str) {
+        if let Some(arg) = self.args.iter_mut().find(|arg| arg.matches(argument)) {
+            arg.default = Some(value.to_string());
+            arg.takes_value = true;
+        }
+        self.refresh_lookups();
+    }
+
+    /// Function used to restrict parameters of an argument to a set of allowed values
+    ///
+    /// Other values make `pars()` display an error listing the allowed ones, which are also shown in the help screen
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--format", "output format");
+    /// args.add_choices("--format", &["json", "yaml", "csv"]);
+    /// ```
+    fn add_choices(&mut self, argument: &str, choices: &[&str]) {
+        if let Some(arg) = self.args.iter_mut().find(|arg| arg.matches(argument)) {
+            arg.choices = choices.iter().map(|c| c.to_string()).collect();
+            arg.takes_value = true;
+        }
+        self.refresh_lookups();
+    }
+
+    /// Function used to split parameters of an argument into multiple values on a delimiter
+    ///
+    /// `get_parameters_for()` then returns the split values (`--features a,b --features c` results in `a`, `b` and `c`)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--features", "features to enable");
+    /// args.set_delimiter("--features", ',');
+    /// for feature in args.get_parameters_for("--features") {
+    ///     println!("enabling {}", feature);
+    /// }
+    /// ```
+    fn set_delimiter(&mut self, argument: &str, delimiter: char) {
+        if let Some(arg) = self.args.iter_mut().find(|arg| arg.matches(argument)) {
+            arg.delimiter = Some(delimiter);
+            arg.takes_value = true;
+        }
+        self.refresh_lookups();
+    }
+
+    /// Function used to set the kind of parameter an argument takes, shown in the help screen
+    /// (`--output <FILE>`) and used by completion scripts to complete file paths, directories or host names
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--output", "file to write to");
+    /// args.set_value_hint("--output", ValueHint::FilePath);
+    /// ```
+    fn set_value_hint(&mut self, argument: &str, hint: ValueHint) {
+        if let Some(arg) = self.args.iter_mut().find(|arg| arg.matches(argument)) {
+            arg.value_hint = Some(hint);
+            arg.takes_value = true;
+        }
+        self.refresh_lookups();
+    }
+
+    /// Function used to set the name of the parameter an argument takes, shown in the help screen
+    /// as `--output <PATH>` (marking the argument as taking a parameter)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--output", "write results to PATH");
+    /// args.set_value_name("--output", "PATH");
+    /// ```
+    fn set_value_name(&mut self, argument: &str, name: &str) {
+        if let Some(arg) = self.args.iter_mut().find(|arg| arg.matches(argument)) {
+            arg.value_name = Some(name.to_string());
+            arg.takes_value = true;
+        }
+        self.refresh_lookups();
+    }
+
+    /// Function used to mark the parameter of an argument as sensitive (see `Arg::secret()`)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--password", "password to log in with");
+    /// args.mark_secret("--password");
+    /// ```
+    fn mark_secret(&mut self, argument: &str) {
+        if let Some(arg) = self.args.iter_mut().find(|arg| arg.matches(argument)) {
+            arg.secret = true;
+        }
+    }
+
+    /// Function used to set an environment variable from which the parameter of an argument is taken
+    /// when the argument isn't passed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--token", "API token");
+    /// args.env_fallback("--token", "APP_TOKEN");
+    /// ```
+    fn env_fallback(&mut self, argument: &str, variable: &str) {
+        if let Some(arg) = self.args.iter_mut().find(|arg| arg.matches(argument)) {
+            arg.env = Some(variable.to_string());
+            arg.takes_value = true;
+        }
+        self.refresh_lookups();
+    }
+
+    /// Function used to load parameters of arguments from a config file
+    ///
+    /// The file contains `key = value` lines, where `key` is an argument name with or without the leading
+    /// dashes (`port = 8080` sets the parameter of `--port`). Lines starting with `#` or `;` and `[section]`
+    /// headers are skipped. Passed arguments and environment variables take precedence over the config file.
+    /// A missing config file is not an error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--port", "port to listen on");
+    /// if let Err(err) = args.config_file("~/.myapp.conf") {
+    ///     eprintln!("ERROR: Cannot read config file: {}", err);
+    /// }
+    /// ```
+    fn config_file(&mut self, path: &str) -> std::io::Result<()> {
+        match std::fs::read_to_string(config::expand_home(path)) {
+            Ok(content) => {
+                self.config_values.extend(config::parse(&content));
+                self.refresh_lookups();
+                Ok(())
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Function used to replace `@file` arguments with arguments read from the file
+    ///
+    /// The file contains arguments separated by whitespace (e.g. one per line), with quotes and
+    /// backslashes working like in a shell. Arguments after the `--` separator, and `@-` (a value read
+    /// with `read_value_or_stdin()`), are not replaced.
+    /// Call it before `pars()`; a file which can't be read is an error
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// if let Err(err) = args.expand_response_files() {
+    ///     eprintln!("ERROR: Cannot read response file: {}", err);
+    ///     std::process::exit(1);
+    /// }
+    /// std::process::exit(args.pars());
+    /// ```
+    fn expand_response_files(&mut self) -> std::io::Result<()> {
+        let mut arguments: Vec<String> = Vec::new();
+        let mut arguments_os: Vec<OsString> = Vec::new();
+        let mut separator_passed: bool = false;
+        for (i, argument) in self.arguments_passed.iter().enumerate() {
+            match argument.strip_prefix('@') {
+                Some(path) if i > 0 && !separator_passed && !path.is_empty() && path != "-" => {
+                    let content: String = std::fs::read_to_string(path)?;
+                    for argument in response_file::split(&content) {
+                        arguments_os.push(OsString::from(&argument));
+                        arguments.push(argument);
+                    }
+                }
+                _ => {
+                    separator_passed |= i > 0 && argument == "--";
+                    arguments.push(argument.clone());
+                    if let Some(argument_os) = self.arguments_passed_os.get(i) {
+                        arguments_os.push(argument_os.clone());
+                    }
+                }
+            }
+        }
+        self.number_of_arguments = arguments.len() as u32;
+        self.arguments_passed = arguments;
+        self.arguments_passed_os = arguments_os;
+        self.refresh_lookups();
+        Ok(())
+    }
+
+    /// Function used to attach a validator to an argument
+    ///
+    /// Every parameter of the argument is checked by `pars()`, which displays an error and returns
+    /// a nonzero value when the validator returns false
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--port", "port to listen on");
+    /// args.add_validator("--port", |v| v.parse::<u16>().is_ok());
+    /// ```
+    fn add_validator<F: Fn(&str) -> bool + 'static>(&mut self, argument: &str, validator: F) {
+        self.validators.push((
+            self.canonical_argument(argument).to_string(),
+            Rc::new(validator),
+        ));
+    }
+
+    /// Function used to allow passing unknown arguments, which are collected instead of causing an error
+    ///
+    /// Unknown arguments, as well as values which are not parameters or positional arguments,
+    /// can be retrived with `unknown_args()`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--dry-run", "only print the command");
+    /// args.allow_unknown(true);
+    /// let forwarded: Vec<&str> = args.unknown_args();
+    /// ```
+    fn allow_unknown(&mut self, allow: bool) {
+        self.allow_unknown = allow;
+    }
+
+    /// Function used to accept unambiguous prefixes of long arguments (`--verb` for `--verbose`)
+    ///
+    /// A prefix matching more than one argument makes `pars()` display an error listing them
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// args.allow_abbreviations(true);
+    /// ```
+    fn allow_abbreviations(&mut self, allow: bool) {
+        self.allow_abbreviations = allow;
+        self.refresh_lookups();
+    }
+
+    /// Function used to match long arguments case-insensitively (`--VERBOSE` for `--verbose`)
+    ///
+    /// Short arguments are still case-sensitive, so that e.g. `-v` and `-V` can be different arguments
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// args.ignore_case(true);
+    /// ```
+    fn ignore_case(&mut self, ignore: bool) {
+        self.ignore_case = ignore;
+        self.refresh_lookups();
+    }
+
+    /// Function used to also accept Windows-style arguments: `/name` for `--name` (or `-n` for `/n`),
+    /// `/name:value` for `--name value` and `/?` for `--help`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_arg(Arg::new("--out", "file to write to").takes_value(true));
+    /// args.windows_options(true);
+    /// // `/out:file.txt` is now the same as `--out file.txt`
+    /// ```
+    fn windows_options(&mut self, enable: bool) {
+        self.windows_options = enable;
+        self.refresh_lookups();
+    }
+
+    /// Function used to accept `help` as the first passed argument, working like `--help`:
+    /// `app help` displays the help screen and `app help TOPIC` displays a help topic
+    /// (or the help of an argument, e.g. `app help --output`)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_help_topic("formats", "Supported formats:\n  json\n  yaml");
+    /// args.help_command(true);
+    /// // `help formats` is now the same as `--help formats`
+    /// ```
+    fn help_command(&mut self, enable: bool) {
+        self.help_command = enable;
+        self.refresh_lookups();
+    }
+
+    /// Function used to make only arguments declared as taking a parameter (with `Arg::takes_value()`,
+    /// `set_default()`, `add_choices()`, ...) take one; all other arguments become flags
+    ///
+    /// Values can then be freely interleaved with arguments: they are bound to positional arguments in order
+    /// instead of being taken as a parameter of the preceding argument
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// args.add_arg(Arg::new("--output", "file to write to").takes_value(true));
+    /// args.add_positional("FIRST", "first file");
+    /// args.add_positional("SECOND", "second file");
+    /// args.strict_values(true);
+    /// // `a.txt --verbose b.txt --output out.txt` binds a.txt to FIRST and b.txt to SECOND
+    /// ```
+    fn strict_values(&mut self, enable: bool) {
+        self.strict_values = enable;
+        self.refresh_lookups();
+    }
+
+    /// Function used to end processing of arguments at the first value which is not a parameter of one,
+    /// as POSIX utilities do: everything passed after it is treated like arguments passed after `--`
+    ///
+    /// It is also enabled by `new()` and `from_os_args()` when the `POSIXLY_CORRECT` environment variable
+    /// is set (other constructors and `parse_tokens()` don't read it)
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --verbose script.sh --debug"));
+    /// args.add_flag("--verbose", "explain what is being done");
+    /// args.add_flag("--debug", "print debugging information");
+    /// args.posix_mode(true);
+    /// assert!(!args.passed("--debug"));
+    /// assert_eq!(args.trailing_args(), ["script.sh", "--debug"]);
+    /// ```
+    fn posix_mode(&mut self, enable: bool) {
+        self.posix_mode = enable;
+        self.refresh_lookups();
+    }
+
+    /// Function used to make `pars()` print how each passed token was classified (option, parameter,
+    /// positional or unknown) to stderr, which can also be enabled with the `ARGPARS_DEBUG=1`
+    /// environment variable
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_arg(Arg::new("--output", "file to write to").takes_value(true));
+    /// args.enable_trace();
+    /// // `--output out.txt` prints:
+    /// // argpars: '--output' -> option '--output'
+    /// // argpars: 'out.txt' -> parameter of '--output'
+    /// args.pars();
+    /// ```
+    fn enable_trace(&mut self) {
+        self.trace = true;
+    }
+
+    /// Function used to enable or disable paging of the help screen (enabled by default)
+    ///
+    /// When enabled, a help screen taller than the terminal is shown through `$PAGER`
+    /// (or `less`, or `more`) if stdout is a terminal
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.paginate_help(false);
+    /// ```
+    fn paginate_help(&mut self, paginate: bool) {
+        self.paginate_help = paginate;
+    }
+
+    /// Function used to set the order of arguments in the help screen (declaration order by default)
+    ///
+    /// Arguments with a display order key (`Arg::display_order()`) are always listed first, by their keys
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// args.add_arg(Arg::new("--output", "file to write to").takes_value(true).display_order(0));
+    /// args.add_argument("--color", "colorize the output");
+    /// args.help_order(HelpOrder::Alphabetical);
+    /// // --output, then --color, --help, --verbose and --version
+    /// ```
+    fn help_order(&mut self, order: HelpOrder) {
+        self.help_order = order;
+    }
+
+    /// Function used to list default arguments (--help, --version) after all other arguments in the help screen
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// args.default_arguments_last(true);
+    /// ```
+    fn default_arguments_last(&mut self, last: bool) {
+        self.default_arguments_last = last;
+    }
+
+    /// Function used to restrict the number of passed positional arguments (including ones passed after `--`)
+    ///
+    /// `pars()` displays an error with the accepted range when too few or too many were passed.
+    /// Values passed beyond registered positional arguments are accepted and returned by `positional_args()`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app a.txt b.txt c.txt d.txt"));
+    /// args.add_positional("FILE", "files to process");
+    /// args.positionals_range(1..=3);
+    /// assert!(matches!(args.try_pars(), Err(ArgparsError::WrongNumberOfPositionals(4, _))));
+    /// ```
+    fn positionals_range<R: RangeBounds<usize>>(&mut self, range: R) {
+        self.positionals_range = (range.start_bound().cloned(), range.end_bound().cloned());
+    }
+
+    /// Function used to retrive values of all passed positional arguments, in order
+    /// (including ones passed after `--`)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.positionals_range(1..);
+    /// for file in args.positional_args() {
+    ///     println!("processing {}", file);
+    /// }
+    /// ```
+    fn positional_args(&self) -> Vec<&str> {
+        let mut indices: Vec<usize> = self.positional_indices();
+        indices.extend(self.trailing_positional_index());
+        indices.sort_unstable();
+        indices
+            .into_iter()
+            .map(|index| self.value_at(index))
+            .collect()
+    }
+
+    /// Function used to retrive passed arguments which are not registered, in order
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.allow_unknown(true);
+    /// for arg in args.unknown_args() {
+    ///     println!("passing {} through", arg);
+    /// }
+    /// ```
+    fn unknown_args(&self) -> Vec<&str> {
+        self.unexpected_tokens()
+    }
+
+    /// Function used to restrict parameters of an argument to a range of numbers
+    ///
+    /// Parameters which are not numbers of the given type make `pars()` display an invalid value error,
+    /// and numbers outside of the range an error with the accepted range
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--threads", "number of threads to use");
+    /// args.range_constraint("--threads", 1..=256);
+    /// ```
+    ///
+    /// The accepted range is described with the messages set when the error is reported:
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --threads 0"));
+    /// args.add_argument("--threads", "number of threads to use");
+    /// args.range_constraint("--threads", 1..);
+    /// args.set_messages(Messages::german());
+    /// let err: ArgparsError = args.try_pars().unwrap_err();
+    /// assert_eq!(err, ArgparsError::OutOfRange("--threads".into(), "0".into(), "mindestens 1".into()));
+    /// ```
+    fn range_constraint<T, R>(&mut self, argument: &str, range: R)
+    where
+        T: FromStr + PartialOrd + fmt::Display + 'static,
+        R: RangeBounds<T> + 'static,
+    {
+        // Bounds are described when an error is reported, with the messages set at that time
+        let bounds: RangeBoundaries = (
+            range.start_bound().map(|start| start.to_string()),
+            range.end_bound().map(|end| end.to_string()),
+        );
+        self.add_validator(argument, |value| value.parse::<T>().is_ok());
+        self.range_constraints.push((
+            self.canonical_argument(argument).to_string(),
+            bounds,
+            Rc::new(move |value| value.parse::<T>().is_ok_and(|value| range.contains(&value))),
+        ));
+    }
+
+    /// Function used to attach a handler to an argument
+    ///
+    /// After successful parsing, `pars()` calls handlers of passed arguments (in the order they were attached)
+    /// with their parameters. A handler returns an exit code: the first nonzero one stops calling
+    /// further handlers and is returned by `pars()`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--print-stuff", "display \"stuff\"");
+    /// args.on("--print-stuff", |_| {
+    ///     println!("stuff");
+    ///     0
+    /// });
+    /// std::process::exit(args.pars());
+    /// ```
+    fn on<F: Fn(&[&str]) -> i32 + 'static>(&mut self, argument: &str, handler: F) {
+        self.handlers.push((
+            self.canonical_argument(argument).to_string(),
+            Rc::new(handler),
+        ));
+    }
+
+    /// Function used to add an extension, which registers its arguments right away
+    ///
+    /// After successful parsing (and calling handlers), `pars()` passes found arguments to every extension,
+    /// in the order they were added
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// struct Logging;
+    ///
+    /// impl ArgparsExtension for Logging {
+    ///     fn register(&self, args: &mut ArgsObj) {
+    ///         args.add_flag("--log", "log what is being done");
+    ///     }
+    ///
+    ///     fn handle(&self, matches: &Matches) {
+    ///         if matches.is_present("--log") {
+    ///             println!("logging enabled");
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.register_extension(Logging);
+    /// std::process::exit(args.pars());
+    /// ```
+    fn register_extension<E: ArgparsExtension + 'static>(&mut self, extension: E) {
+        extension.register(self);
+        self.extensions.push(Rc::new(extension));
+    }
+
+    /// Function used to add a group of mutually exclusive arguments
+    ///
+    /// `pars()` displays an error and returns a nonzero value when more than one of them was passed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--json", "output in JSON");
+    /// args.add_argument("--yaml", "output in YAML");
+    /// args.add_exclusive_group(&["--json", "--yaml"]);
+    /// ```
+    fn add_exclusive_group(&mut self, group: &[&str]) {
+        let group: Vec<String> = group
+            .iter()
+            .map(|arg| self.canonical_argument(arg).to_string())
+            .collect();
+        self.exclusive_groups.push(group);
+    }
+
+    /// Function used to declare that an argument can only be used together with another one
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--output", "file to write to");
+    /// args.add_argument("--format", "format of the output file");
+    /// args.requires("--output", "--format");
+    /// ```
+    fn requires(&mut self, argument: &str, required: &str) {
+        self.requirements.push((
+            self.canonical_argument(argument).to_string(),
+            self.canonical_argument(required).to_string(),
+        ));
+    }
+
+    /// Function used to declare that an argument is required when another one is passed
+    ///
+    /// Like for required arguments, an environment variable or config file value is enough
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --tls"));
+    /// args.add_flag("--tls", "use TLS");
+    /// args.add_argument("--key-file", "private key for TLS");
+    /// args.required_if("--key-file", "--tls");
+    /// assert_eq!(
+    ///     args.try_pars().unwrap_err(),
+    ///     ArgparsError::MissingRequiredArgument("--key-file".to_string())
+    /// );
+    /// ```
+    fn required_if(&mut self, argument: &str, condition: &str) {
+        self.conditional_requirements.push((
+            self.canonical_argument(argument).to_string(),
+            self.canonical_argument(condition).to_string(),
+        ));
+    }
+
+    /// Function used to declare that two arguments cannot be used together
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--quiet", "print nothing");
+    /// args.add_argument("--verbose", "print everything");
+    /// args.conflicts_with("--quiet", "--verbose");
+    /// ```
+    fn conflicts_with(&mut self, argument: &str, conflicting: &str) {
+        self.add_exclusive_group(&[argument, conflicting]);
+    }
+
+    /// Function used to keep accepting an old name of an argument, warning that it is deprecated
+    ///
+    /// The old name works like an alias of the new one, and `pars()` prints a warning pointing to the new name
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--color", "colorize the output");
+    /// args.deprecate_alias("--colour", "--color");
+    /// ```
+    fn deprecate_alias(&mut self, alias: &str, replacement: &str) {
+        self.deprecated_aliases
+            .push((alias.to_string(), replacement.to_string()));
+        self.refresh_lookups();
+    }
+
+    /// Function used to add an optional positional argument into the app
+    ///
+    /// Positional arguments are bound to passed values which are not parameters of other arguments,
+    /// in the order they were added
+    ///
+    /// By default, an argument takes the value passed after it as its parameter, so values interleaved
+    /// with arguments (`file1 --verbose file2`) are only bound to positional arguments after enabling
+    /// `strict_values()`, which makes arguments not declared as taking a parameter flags
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_positional("FILE", "input file");
+    /// ```
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app file1 --verbose file2"));
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// args.add_positional("FIRST", "first file");
+    /// args.add_positional("SECOND", "second file");
+    /// assert_eq!(args.get_parameter_for("--verbose"), "file2");
+    /// args.strict_values(true);
+    /// assert_eq!(args.get_positional("SECOND"), "file2");
+    /// ```
+    fn add_positional(&mut self, name: &str, description: &str) {
+        self.positionals.push(Positional {
+            name: name.to_string(),
+            description: description.to_string(),
+            required: false,
+            trailing: false,
+        });
+    }
+
+    /// Function used to add a positional argument which has to be passed for the app to run
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_required_positional("FILE", "input file");
+    /// ```
+    fn add_required_positional(&mut self, name: &str, description: &str) {
+        self.positionals.push(Positional {
+            name: name.to_string(),
+            description: description.to_string(),
+            required: true,
+            trailing: false,
+        });
+    }
+
+    /// Function used to add a positional argument bound to the last passed value, wherever other
+    /// positional arguments end (e.g. the `TARGET` of `app --jobs 4 SOURCE... TARGET`)
+    ///
+    /// The last value is never taken as a parameter of a preceding argument, so `app --output TARGET`
+    /// reports `--output` as missing its value. Only one trailing positional argument can be added
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_positional("SOURCE", "file to copy");
+    /// args.trailing_positional("TARGET", "where to copy the file", true);
+    /// args.pars();
+    /// println!("copying to {}", args.get_positional("TARGET"));
+    /// ```
+    fn trailing_positional(&mut self, name: &str, description: &str, required: bool) {
+        self.positionals.push(Positional {
+            name: name.to_string(),
+            description: description.to_string(),
+            required,
+            trailing: true,
+        });
+    }
+
+    /// Function used to retrive the value bound to a positional argument (empty if it wasn't passed)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_positional("FILE", "input file");
+    /// println!("input file: {}", args.get_positional("FILE"));
+    /// ```
+    fn get_positional(&self, name: &str) -> &str {
+        self.bound_positionals()
+            .into_iter()
+            .find(|(positional, _)| positional.name == name)
+            .map_or("", |(_, index)| self.value_at(index))
+    }
+
+    /// Function which checks if the parameter of an argument, or the value of a positional argument,
+    /// is a lone `-`, which by convention means stdin (or stdout)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_arg(Arg::new("--input", "file to read from").takes_value(true));
+    /// if args.value_is_stdin("--input") {
+    ///     println!("reading from stdin");
+    /// }
+    /// ```
+    fn value_is_stdin(&self, arg: &str) -> bool {
+        if self.positionals.iter().any(|p| p.name == arg) {
+            self.get_positional(arg) == "-"
+        } else {
+            self.get_parameter_for(arg) == "-"
+        }
+    }
+
+    /// Function used to retrive the parameter of an argument, or the value of a positional argument,
+    /// reading it from stdin when `-` or `@-` was passed instead
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_arg(Arg::new("--script", "script to run").takes_value(true));
+    /// // `--script 'echo hi'` or `--script - < script.sh`
+    /// match args.read_value_or_stdin("--script") {
+    ///     Ok(script) => println!("running: {}", script),
+    ///     Err(err) => eprintln!("ERROR: Cannot read the script from stdin: {}", err),
+    /// }
+    /// ```
+    fn read_value_or_stdin(&self, arg: &str) -> std::io::Result<String> {
+        let value: &str = if self.positionals.iter().any(|p| p.name == arg) {
+            self.get_positional(arg)
+        } else {
+            self.get_parameter_for(arg)
+        };
+        if value != "-" && value != "@-" {
+            return Ok(value.to_string());
+        }
+        let mut content: String = String::new();
+        std::io::stdin().read_to_string(&mut content)?;
+        Ok(content)
+    }
+
+    /// Function used to retrive arguments passed after the `--` separator
+    ///
+    /// They are never treated as arguments, but are still bound to positional arguments
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// let child_args: &[String] = args.trailing_args();
+    /// ```
+    fn trailing_args(&self) -> &[String] {
+        &self.trailing_arguments
+    }
+
+    /// Function returning if default arguments were passed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// if args.default_arguments_passed() {
+    ///     println!("default arguments passed")
+    /// }
+    /// ```
+    fn default_arguments_passed(&self) -> bool {
+        (self.default_argument("--help") && self.passed("--help"))
+            || (self.default_argument("--version") && self.passed("--version"))
+            || self.completion_request().is_some()
+    }
+
+    /// Function returning if wrong (non existent or invalid) arguments / parameters were passed,
+    /// or if any required argument or positional argument is missing
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// if args.wrong_arguments_passed() {
+    ///     println!("wrong arguments passed")
+    /// }
+    /// ```
+    fn wrong_arguments_passed(&self) -> bool {
+        self.ambiguous_abbreviation().is_some()
+            || self.first_unexpected_token().is_some()
+            || self.missing_required_argument().is_some()
+            || self.missing_required_positional().is_some()
+            || self.wrong_number_of_positionals().is_some()
+            || self.invalid_value().is_some()
+            || self.invalid_choice().is_some()
+            || self.out_of_range().is_some()
+            || self.conflicting_arguments().is_some()
+            || self.missing_dependency().is_some()
+    }
+
+    /// Function used to retrive passed tokens which are wrong, in order: unknown options (unless allowed),
+    /// ambiguous abbreviations and parameters rejected by a validator, possible values, range or `KEY=VALUE` format
+    ///
+    /// Missing, conflicting or dependent arguments don't have a wrong token, see `wrong_arguments_passed()`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --colour --jobs many"));
+    /// args.add_argument("--jobs", "number of jobs");
+    /// args.add_validator("--jobs", |value| value.parse::<u32>().is_ok());
+    /// assert_eq!(args.wrong_arguments(), ["--colour", "many"]);
+    /// ```
+    ///
+    /// Tokens holding parameters of secret arguments are replaced with `***`:
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --token hunter2 --password=hunter2"));
+    /// args.add_arg(Arg::new("--token", "API token").choices(&["a", "b"]).secret(true));
+    /// args.add_arg(Arg::new("--password", "ask for a password").takes_value(false).secret(true));
+    /// assert_eq!(args.wrong_arguments(), ["***", "***"]);
+    /// assert_eq!(
+    ///     args.try_pars().unwrap_err(),
+    ///     ArgparsError::UnknownOption("--password=***".to_string())
+    /// );
+    /// ```
+    fn wrong_arguments(&self) -> Vec<&str> {
+        let unexpected: Vec<&str> = if self.allow_unknown {
+            Vec::new()
+        } else {
+            self.unexpected_tokens()
+        };
+        let rejected: Vec<&str> = self.rejected_parameters();
+        self.arguments_expanded
+            .iter()
+            .enumerate()
+            .skip(1)
+            .filter(|(i, token)| {
+                unexpected.contains(&token.as_str())
+                    || (self.abbreviated_arguments(token).len() > 1
+                        && self.exact_argument(token).is_none())
+                    || (self.parameter_owner(*i).is_some() && rejected.contains(&token.as_str()))
+            })
+            .map(|(i, token)| {
+                let secret: bool = (!self.is_option_token(token)
+                    && self.parameter_owner(i).is_some_and(|arg| arg.secret))
+                    || self.shown_token(token) != *token;
+                if secret {
+                    REDACTED
+                } else {
+                    token.as_str()
+                }
+            })
+            .collect()
+    }
+
+    /// Function used to retrive the first passed token which is wrong (see `wrong_arguments()`), if any
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// if let Some(token) = args.first_wrong_argument() {
+    ///     eprintln!("bad invocation near '{}'", token);
+    /// }
+    /// ```
+    fn first_wrong_argument(&self) -> Option<&str> {
+        self.wrong_arguments().first().copied()
+    }
+
+    /// Function used to retrive passed parameter to an argument
+    ///
+    /// Both `--arg value` and `--arg=value` forms are supported. If the argument was passed more than once,
+    /// the parameter of its first occurrence is returned (see `get_last_parameter_for()`). If the argument or its parameter
+    /// wasn't passed, the environment variable set with `env_fallback()`, the value from the config file
+    /// loaded with `config_file()` or the default set with `set_default()` (or an empty string) is returned.
+    /// Use `try_get_parameter_for()` to find out whether the argument was passed at all
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// println!("parameter for --help: {}", args.get_parameter_for("--help"));
+    /// ```
+    fn get_parameter_for(&self, arg: &str) -> &str {
+        let canonical: &str = self.canonical_argument(arg);
+        self.passed_parameter(canonical)
+            .or_else(|| self.fallback_value(canonical))
+            .unwrap_or_default()
+    }
+
+    /// Function used to retrive passed parameter to the last occurrence of an argument ("last one wins")
+    ///
+    /// Like `get_parameter_for()`, the fallback value is returned if the argument or its parameter wasn't passed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--mode", "mode of operation");
+    /// // `--mode fast --mode safe` results in "safe"
+    /// println!("mode: {}", args.get_last_parameter_for("--mode"));
+    /// ```
+    fn get_last_parameter_for(&self, arg: &str) -> &str {
+        let canonical: &str = self.canonical_argument(arg);
+        if let Some(index_of_argument) = self.parameter_positions_of(canonical).last() {
+            let parameters: &[String] = &self.arguments_expanded[..self.parameters_end()];
+            if let Some(parameter) = parameters.get(index_of_argument + 1) {
+                if !self.is_known_argument(parameter) {
+                    return parameter;
+                }
+            }
+        }
+        self.fallback_value(self.canonical_argument(arg))
+            .unwrap_or_default()
+    }
+
+    /// Function used to retrive passed parameter to an argument as it was passed, even if it is not valid UTF-8
+    ///
+    /// Like `get_parameter_for()`, the fallback value (or an empty string) is returned if the argument
+    /// or its parameter wasn't passed
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_os_args();
+    /// args.add_argument("--input", "file to read from");
+    /// let path: &std::path::Path = std::path::Path::new(args.get_parameter_os_for("--input"));
+    /// ```
+    fn get_parameter_os_for(&self, arg: &str) -> &OsStr {
+        let canonical: &str = self.canonical_argument(arg);
+        let separator: usize = self.options_end.min(self.arguments_passed.len());
+        // The last value is bound to a trailing positional argument instead
+        let separator: usize = if self.parameters_end() < self.arguments_expanded.len() {
+            separator.saturating_sub(1)
+        } else {
+            separator
+        };
+        for i in 1..separator.min(self.arguments_passed_os.len()) {
+            let token: &str = &self.arguments_passed[i];
+            if self.canonical_argument(token) == canonical {
+                if i + 1 < separator
+                    && !self
+                        .find_argument(canonical)
+                        .is_some_and(|a| self.is_flag(a))
+                    && !self.is_known_argument(&self.arguments_passed[i + 1])
+                {
+                    if let Some(parameter) = self.arguments_passed_os.get(i + 1) {
+                        return parameter;
+                    }
+                }
+                break;
+            }
+            if let Some((key, _)) = self.split_key_value(token) {
+                let bytes: &[u8] = self.arguments_passed_os[i].as_encoded_bytes();
+                // arguments_passed_os can be changed separately from arguments_passed
+                let split: bool =
+                    bytes.starts_with(key.as_bytes()) && bytes.get(key.len()) == Some(&b'=');
+                if split && self.canonical_argument(key) == canonical {
+                    // SAFETY: the bytes are split right after an ASCII '=' character
+                    return unsafe { OsStr::from_encoded_bytes_unchecked(&bytes[key.len() + 1..]) };
+                }
+            }
+        }
+        OsStr::new(self.fallback_value(canonical).unwrap_or_default())
+    }
+
+    /// Function used to retrive passed parameter to an argument, or `None` if the argument wasn't passed
+    ///
+    /// An argument passed without a parameter results in `Some("")`, unless a fallback value
+    /// (environment variable, config file or default) exists
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--output", "file to write to");
+    /// match args.try_get_parameter_for("--output") {
+    ///     Some("") => println!("--output passed without a file"),
+    ///     Some(file) => println!("writing to {}", file),
+    ///     None => println!("--output not passed"),
+    /// }
+    /// ```
+    fn try_get_parameter_for(&self, arg: &str) -> Option<&str> {
+        let parameter: &str = self.get_parameter_for(arg);
+        if parameter.is_empty() && !self.passed(arg) {
+            None
+        } else {
+            Some(parameter)
+        }
+    }
+
+    /// Function used to retrive the parameter of an argument together with where it comes from, or `None`
+    /// if the argument wasn't passed and has no fallback value
+    ///
+    /// The command line takes precedence over the environment variable, which takes precedence over
+    /// the config file, which takes precedence over the default
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app"));
+    /// args.add_arg(Arg::new("--port", "port to listen on").default("8080"));
+    /// assert_eq!(args.resolved_value("--port"), Some(("8080", ValueSource::Default)));
+    /// ```
+    fn resolved_value(&self, arg: &str) -> Option<(&str, ValueSource)> {
+        let canonical: &str = self.canonical_argument(arg);
+        if let Some(parameter) = self.passed_parameter(canonical) {
+            return Some((parameter, ValueSource::CommandLine));
+        }
+        match self.fallback_source(canonical) {
+            Some(fallback) => Some(fallback),
+            None if self.passed(canonical) => Some(("", ValueSource::CommandLine)),
+            None => None,
+        }
+    }
+
+    /// Function used to retrive all passed parameters to an argument
+    ///
+    /// Parameters of every occurrence of the argument are collected (`--file a --file b`),
+    /// as well as all values following an argument added with `add_multi_value_argument()`.
+    /// If none were passed, the environment variable, config file or default value is returned
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_multi_value_argument("--file", "files to process");
+    /// for file in args.get_parameters_for("--file") {
+    ///     println!("processing {}", file);
+    /// }
+    /// ```
+    fn get_parameters_for(&self, arg: &str) -> Vec<&str> {
+        let canonical: &str = self.canonical_argument(arg);
+        let multi_value: bool = self.find_argument(canonical).is_some_and(|a| a.multi_value);
+        let delimiter: Option<char> = self.find_argument(canonical).and_then(|a| a.delimiter);
+        let mut parameters: Vec<&str> = Vec::new();
+        for &index_of_argument in self.parameter_positions_of(canonical) {
+            for parameter in &self.arguments_expanded[index_of_argument + 1..self.parameters_end()]
+            {
+                if multi_value && self.is_option_token(parameter) {
+                    break;
+                }
+                if !multi_value && self.is_known_argument(parameter) {
+                    break;
+                }
+                parameters.push(parameter);
+                if !multi_value {
+                    break;
+                }
+            }
+        }
+        if parameters.is_empty() {
+            if let Some(fallback) = self.fallback_value(canonical) {
+                parameters.push(fallback);
+            }
+        }
+        match delimiter {
+            Some(delimiter) => parameters
+                .into_iter()
+                .flat_map(|p| p.split(delimiter))
+                .filter(|p| !p.is_empty())
+                .collect(),
+            None => parameters,
+        }
+    }
+
+    /// Function used to retrive keys and values passed with an argument added with `add_prefix_argument()`, in order
+    ///
+    /// Both `-Dkey=value` and `-D key=value` are accepted; the value is empty if there is no `=`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_prefix_argument("-D", "define a property");
+    /// for (key, value) in args.prefix_values("-D") {
+    ///     println!("{} = {}", key, value);
+    /// }
+    /// ```
+    fn prefix_values(&self, prefix: &str) -> Vec<(&str, &str)> {
+        let canonical: &str = self.canonical_argument(prefix);
+        let mut values: Vec<(&str, &str)> = Vec::new();
+        let mut tokens = self.arguments_expanded.iter().skip(1);
+        while let Some(token) = tokens.next() {
+            if self.canonical_argument(token) != canonical {
+                continue;
+            }
+            let definition: &str = match token.strip_prefix(canonical) {
+                Some("") => match tokens.next() {
+                    Some(next) => next,
+                    None => break,
+                },
+                Some(definition) => definition,
+                None => continue,
+            };
+            values.push(definition.split_once('=').unwrap_or((definition, "")));
+        }
+        values
+    }
+
+    /// Function used to retrive parameters of an argument taking `KEY=VALUE` parameters as a map
+    ///
+    /// When a key is passed more than once, the last value wins
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --env HOME=/root --env TERM=xterm"));
+    /// args.add_map_argument("--env", "set an environment variable");
+    /// assert_eq!(args.get_map_for("--env").get("TERM").map(|v| v.as_str()), Some("xterm"));
+    /// ```
+    fn get_map_for(&self, arg: &str) -> HashMap<String, String> {
+        self.get_parameters_for(arg)
+            .into_iter()
+            .filter_map(|parameter| parameter.split_once('='))
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+            .collect()
+    }
+
+    /// Function used to export passed arguments, positional arguments and arguments passed after
+    /// the `--` separator in a single map (see `ParsedArgs::to_map()`)
+    ///
+    /// Arguments which weren't passed are left out, even if they have a default value
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    /// use std::collections::HashMap;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_arg(Arg::new("--output", "file to write to").takes_value(true));
+    /// args.add_positional("INPUT", "file to read from");
+    /// let map: HashMap<String, Vec<String>> = args.to_map();
+    /// if let Some(output) = map.get("--output") {
+    ///     println!("output: {:?}", output);
+    /// }
+    /// ```
+    fn to_map(&self) -> HashMap<String, Vec<String>> {
+        self.matches().to_map()
+    }
+
+    /// Function used to retrive passed parameter to an argument converted into a given type
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--port", "port to listen on");
+    /// match args.get_parameter_as::<u16>("--port") {
+    ///     Ok(port) => println!("listening on port {}", port),
+    ///     Err(err) => eprintln!("ERROR: {}", err),
+    /// }
+    /// ```
+    fn get_parameter_as<T: FromStr>(&self, arg: &str) -> Result<T, ParseError>
+    where
+        T::Err: fmt::Display,
+    {
+        let value: &str = self.get_parameter_for(arg);
+        value.parse::<T>().map_err(|err| ParseError {
+            argument: arg.to_string(),
+            value: value.to_string(),
+            reason: err.to_string(),
+        })
+    }
+
+    /// Function used to display error messages, followed by the usage line and a pointer to the help screen
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.display_error_message(&ArgparsError::UnknownOption("--foo".to_string()));
+    /// ```
+    fn display_error_message(&self, err: &ArgparsError) {
+        let colored: bool = self.colors_enabled(style::Stream::Stderr);
+        let suggestion: Option<&str> = match err {
+            ArgparsError::UnknownOption(option) if option.starts_with('-') => {
+                self.suggestion_for(option)
+            }
+            _ => None,
+        };
+        if self.error_format == ErrorFormat::Json {
+            let token: &str = json::error_token(err);
+            // Parameters are looked up among parameters of the argument, as those of secret arguments are redacted
+            let owner: Option<&str> = match err {
+                ArgparsError::InvalidValue(arg, _)
+                | ArgparsError::InvalidChoice(arg, _, _)
+                | ArgparsError::OutOfRange(arg, _, _) => Some(arg),
+                _ => None,
+            };
+            // Missing arguments were not passed, so they have no position
+            let position: Option<usize> = match err {
+                ArgparsError::MissingRequiredArgument(_)
+                | ArgparsError::MissingRequiredPositional(_)
+                | ArgparsError::WrongNumberOfPositionals(_, _) => None,
+                _ => self
+                    .redacted_expanded()
+                    .iter()
+                    .enumerate()
+                    .skip(1)
+                    .find(|(i, a)| {
+                        *a == token
+                            && owner.is_none_or(|owner| {
+                                self.parameter_owner(*i)
+                                    .is_some_and(|arg| arg.matches(owner))
+                            })
+                    })
+                    .map(|(i, _)| i)
+                    .or_else(|| self.positions_of(token).first().copied()),
+            };
+            self.write(
+                style::Stream::Stderr,
+                &json::error(err, position, suggestion, &self.messages.error_message(err)),
+            );
+            return;
+        }
+        let label: String = style::paint(&self.messages.error, style::ERROR, colored);
+        let error: String = self.messages.error_message(err);
+        let message: String = match suggestion {
+            Some(suggestion) => format!(
+                "{} {}. {}\n",
+                label,
+                error,
+                self.messages.did_you_mean(suggestion)
+            ),
+            None => format!("{} {}\n", label, error),
+        };
+        self.write(style::Stream::Stderr, &message);
+        self.write(
+            style::Stream::Stderr,
+            &format!("{}\n", self.usage().trim_end()),
+        );
+        // Pointing to the help screen only if --help wasn't disabled
+        if self.default_argument("--help") {
+            self.write(
+                style::Stream::Stderr,
+                &format!(
+                    "{}\n",
+                    self.messages
+                        .try_help(self.arguments_passed.first().map_or("", |p| p.as_str()))
+                ),
+            );
+        }
+    }
+
+    /// Function used to display the help screen
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.display_help_screen();
+    /// ```
+    fn display_help_screen(&self) {
+        let colored: bool = self.colors_enabled(style::Stream::Stdout);
+        self.write_help(&self.help_text(layout::terminal_width(), colored));
+    }
+
+    /// Function returning the help screen (without colors) wrapped to a given width, instead of displaying it
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// let help: String = args.render_help(60);
+    /// assert!(help.contains("--verbose"));
+    /// ```
+    fn render_help(&self, width: usize) -> String {
+        self.help_text(width, false)
+    }
+
+    /// Function returning the usage line: help_usage if it was set, otherwise one generated from
+    /// registered arguments (optional ones in brackets) and positional arguments (required ones in angle brackets)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_required_argument("--input", "file to read from");
+    /// args.add_positional("OUTPUT", "file to write to");
+    /// // Usage: app [--help] [-V|--version] --input [OUTPUT]
+    /// print!("{}", args.usage());
+    /// ```
+    fn usage(&self) -> String {
+        if !self.help_usage.is_empty() {
+            return self.help_usage.clone();
+        }
+        let mut usage: String = format!(
+            "{} {}",
+            self.messages.usage,
+            self.arguments_passed.first().map_or("", |p| p.as_str())
+        );
+        for arg in self.args.iter().filter(|arg| !arg.hidden) {
+            let mut names: Vec<&str> = arg.aliases.iter().map(|a| a.as_str()).collect();
+            names.push(&arg.name);
+            let mut item: String = names.join("|");
+            if let Some(value) = arg.placeholder() {
+                item.push_str(&format!(" <{}>", value));
+                if arg.multi_value {
+                    item.push_str("...");
+                }
+            }
+            if arg.prefix {
+                item.push_str("<key>=<value>...");
+            }
+            if arg.required {
+                usage.push_str(&format!(" {}", item));
+            } else {
+                usage.push_str(&format!(" [{}]", item));
+            }
+        }
+        let positionals = self.positionals.iter().filter(|p| !p.trailing);
+        for positional in positionals.chain(self.positionals.iter().filter(|p| p.trailing)) {
+            if positional.required {
+                usage.push_str(&format!(" <{}>", positional.name));
+            } else {
+                usage.push_str(&format!(" [{}]", positional.name));
+            }
+        }
+        usage.push('\n');
+        usage
+    }
+
+    /// Function used to replace the layout of the help screen with a template
+    ///
+    /// The `{name}`, `{version}`, `{description}`, `{usage}`, `{options}`, `{positionals}`, `{sections}`,
+    /// `{before_help}` and `{after_help}` placeholders are replaced with the app info, arguments,
+    /// help sections and free-text blocks
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.set_help_template("{name} {version}\n{usage}\n\nOPTIONS:\n{options}\n\n{sections}");
+    /// ```
+    fn set_help_template(&mut self, template: &str) {
+        self.help_template = Some(template.to_string());
+    }
+
+    /// Function used to replace the version information displayed by `--version` with a template
+    ///
+    /// The `{name}` and `{version}` placeholders are replaced with the app info, and `{os}` and `{arch}`
+    /// with the platform the app runs on. Build information can be put in with `env!()` or `option_env!()`
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.set_version_template(&format!(
+    ///     "{{name}} {{version}} ({{os}}-{{arch}}, commit {})",
+    ///     option_env!("GIT_HASH").unwrap_or("unknown")
+    /// ));
+    /// ```
+    fn set_version_template(&mut self, template: &str) {
+        self.version_template = Some(template.to_string());
+    }
+
+    /// Function used to display the version information
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.help_version = "v1.0".to_string();
+    /// args.display_version();
+    /// ```
+    fn display_version(&self) {
+        let version: String = match &self.version_template {
+            Some(template) => template
+                .replace("{name}", &self.help_name)
+                .replace("{version}", &self.help_version)
+                .replace("{os}", std::env::consts::OS)
+                .replace("{arch}", std::env::consts::ARCH),
+            None => format!("{} version: {}", self.help_name, self.help_version),
+        };
+        self.write(style::Stream::Stdout, &format!("{}\n", version));
+    }
+
+    /// Function used to add a detailed help page, displayed by `pars()` when `--help <topic>` is passed
+    ///
+    /// The topic can be a name (`--help formats`) or an argument (`--help --output`), in which case
+    /// the page is displayed under the argument description
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--output", "file to write to");
+    /// args.add_help_topic("formats", "Supported formats:\n  json\n  yaml");
+    /// args.add_help_topic("--output", "The file is created if it doesn't exist.");
+    /// ```
+    fn add_help_topic(&mut self, topic: &str, content: &str) {
+        self.help_topics
+            .push((topic.to_string(), content.to_string()));
+    }
+
+    /// Function used to display a detailed help page of a topic or an argument
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_help_topic("formats", "Supported formats:\n  json\n  yaml");
+    /// args.display_help_topic("formats");
+    /// ```
+    ///
+    /// Hidden arguments are treated like unknown topics:
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let hidden: &str = "--dump-state-of-the-internal-cache";
+    /// let mut args: ArgsObj = Argpars::from_vec(vec!["app".to_string(), "--help".to_string(), hidden.to_string()]);
+    /// args.add_arg(Arg::new(hidden, "internal").hidden(true));
+    /// args.display_help_topic(hidden);
+    /// assert_eq!(args.pars(), 0);
+    /// ```
+    fn display_help_topic(&self, topic: &str) {
+        let colored: bool = self.colors_enabled(style::Stream::Stdout);
+        let content: Option<&str> = self.help_topic_content(topic);
+        if let Some(arg) = self.help_topic_argument(topic) {
+            let (plain, painted) = Self::help_names(arg, colored);
+            self.write(
+                style::Stream::Stdout,
+                &self.help_row(
+                    &plain,
+                    &painted,
+                    arg.description.trim(),
+                    layout::terminal_width(),
+                ),
+            );
+            if let Some(content) = content {
+                self.write(style::Stream::Stdout, &format!("\n{}\n", content));
+            }
+        } else if let Some(content) = content {
+            self.write(style::Stream::Stdout, &format!("{}\n", content));
+        }
+    }
+
+    /// Function used to set when to use colors in the help screen and error messages
+    ///
+    /// By default (`ColorChoice::Auto`) colors are used when writing to a terminal,
+    /// unless the `NO_COLOR` environment variable is set
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.set_color(ColorChoice::Never);
+    /// ```
+    fn set_color(&mut self, choice: ColorChoice) {
+        self.color = choice;
+    }
+
+    /// Function used to set the format of error messages, e.g. JSON for wrappers and IDE integrations
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.error_format(ErrorFormat::Json);
+    /// // `app --verbos` prints:
+    /// // {"kind": "unknown_option", "token": "--verbos", "position": 1, "suggestion": "--verbose", "message": "..."}
+    /// std::process::exit(args.pars());
+    /// ```
+    fn error_format(&mut self, format: ErrorFormat) {
+        self.error_format = format;
+    }
+
+    /// Function used to replace built-in strings (error messages, help screen headings, ...), e.g. to translate them
+    ///
+    /// Descriptions of `--help` and `--version` are updated as well, unless they were changed; call it before
+    /// adding negatable arguments to translate descriptions of their `--no-` forms
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.set_messages(Messages {
+    ///     unknown_option: "Nieznana opcja: '{option}'".to_string(),
+    ///     ..Messages::english()
+    /// });
+    /// // Or use a built-in set of messages in the language of the user
+    /// args.set_messages(Messages::from_env());
+    /// ```
+    fn set_messages(&mut self, messages: Messages) {
+        for arg in &mut self.args {
+            if arg.name == "--help" && arg.description == self.messages.help_description {
+                arg.description = messages.help_description.clone();
+            }
+            if arg.name == "--version" && arg.description == self.messages.version_description {
+                arg.description = messages.version_description.clone();
+            }
+        }
+        self.messages = messages;
+    }
+
+    /// Function used to redirect the help screen, help topics and version information into a writer
+    ///
+    /// With `ColorChoice::Auto`, colors are not used in redirected output
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.set_output(std::fs::File::create("help.txt").unwrap());
+    /// args.display_help_screen();
+    /// ```
+    fn set_output<W: Write + 'static>(&mut self, writer: W) {
+        self.output = Some(Rc::new(RefCell::new(writer)));
+    }
+
+    /// Function used to redirect error messages and warnings into a writer
+    ///
+    /// With `ColorChoice::Auto`, colors are not used in redirected output
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.set_error_output(std::fs::File::create("errors.log").unwrap());
+    /// std::process::exit(args.pars());
+    /// ```
+    fn set_error_output<W: Write + 'static>(&mut self, writer: W) {
+        self.error_output = Some(Rc::new(RefCell::new(writer)));
+    }
+
+    /// Function used to set exit codes returned by `pars()` for each class of errors
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.exit_codes(ExitCodes {
+    ///     usage_error: 64,
+    ///     ..Default::default()
+    /// });
+    /// std::process::exit(args.pars());
+    /// ```
+    fn exit_codes(&mut self, codes: ExitCodes) {
+        self.exit_codes = codes;
+    }
+
+    /// Function used to add a section into the help screen
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_help_section("TEST SECTION:", "\tthis is a test section!\n");
+    /// ```
+    fn add_help_section(&mut self, section: &str, content: &str) {
+        self.help_sections.push(section.to_string());
+        self.help_sections_content.push(section.to_string());
+        self.help_sections_content.push(content.to_string());
+    }
+
+    /// Function used to set a free-text block displayed in the help screen right before the list of arguments
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.set_before_help("Reads FILE and writes a summary of it.");
+    /// ```
+    fn set_before_help(&mut self, text: &str) {
+        self.before_help = text.to_string();
+    }
+
+    /// Function used to set a free-text block displayed at the end of the help screen (e.g. examples or a copyright notice)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.set_after_help("Examples:\n  app --output out.txt in.txt\n\nCopyright (c) 2024 Test App authors");
+    /// ```
+    fn set_after_help(&mut self, text: &str) {
+        self.after_help = text.to_string();
+    }
+
+    /// Function used to generate a completion script for a given shell from the registered arguments
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--print-stuff", "display \"stuff\"");
+    /// print!("{}", args.generate_completions(Shell::Zsh));
+    /// ```
+    fn generate_completions(&self, shell: Shell) -> String {
+        let args: Vec<Arg> = self
+            .args
+            .iter()
+            .filter(|arg| !arg.hidden)
+            .cloned()
+            .collect();
+        completions::generate(shell, &self.program_name(), &args)
+    }
+
+    /// Function used to enable dynamic completion: when the first passed argument is the hidden
+    /// `--argpars-complete <shell> <line> <point>` flag, `pars()` prints completion candidates
+    /// for the word under the cursor (`point` characters into `line`) instead of parsing arguments
+    ///
+    /// Unlike scripts from `generate_completions()`, the shell asks the app itself, so completions
+    /// always match the registered arguments. `default_arguments_passed()` returns true in this case
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_arg(Arg::new("--output", "file to write to").value_hint(ValueHint::FilePath));
+    /// args.dynamic_completions(true);
+    /// // In bash:
+    /// // _app() { COMPREPLY=($(app --argpars-complete bash "$COMP_LINE" "$COMP_POINT")); }
+    /// // complete -o default -F _app app
+    /// std::process::exit(args.pars());
+    /// ```
+    fn dynamic_completions(&mut self, enable: bool) {
+        self.dynamic_completions = enable;
+    }
+
+    /// Function returning completion candidates for the word under the cursor of a command line
+    /// (`point` characters into it): arguments when the word starts with a '-', or possible values,
+    /// files or directories when it's a parameter of an argument
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(vec!["app".to_string()]);
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// args.add_argument("--color", "when to use colors");
+    /// args.add_choices("--color", &["auto", "always", "never"]);
+    /// assert_eq!(args.completion_candidates("app --verb", 10), ["--verbose"]);
+    /// assert_eq!(args.completion_candidates("app --color a", 13), ["auto", "always"]);
+    /// ```
+    fn completion_candidates(&self, line: &str, point: usize) -> Vec<String> {
+        completions::candidates(self, line, point)
+            .into_iter()
+            .map(|(candidate, _)| candidate)
+            .collect()
+    }
+
+    /// Function used to generate a man page (in roff format) from the app info, arguments and help sections
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.help_description = "This is a test description".to_string();
+    /// std::fs::write("app.1", args.generate_man_page()).unwrap();
+    /// ```
+    fn generate_man_page(&self) -> String {
+        man::generate(self, &self.program_name())
+    }
+
+    /// Function used to generate a machine-readable (JSON) description of the app and its arguments
+    ///
+    /// Hidden arguments are not included
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--print-stuff", "display \"stuff\"");
+    /// std::fs::write("cli.json", args.to_json_spec()).unwrap();
+    /// ```
+    fn to_json_spec(&self) -> String {
+        json::generate(self, &self.program_name())
+    }
+
+    /// Function used to generate a Markdown reference of the app: usage, options, positional arguments
+    /// and help sections
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--print-stuff", "display \"stuff\"");
+    /// std::fs::write("docs/cli.md", args.to_markdown()).unwrap();
+    /// ```
+    fn to_markdown(&self) -> String {
+        markdown::generate(self, &self.program_name())
+    }
+
+    /// Main Argpars parser
+    ///
+    /// Returns 0 on success, or the exit code of the error class (1 unless changed with `exit_codes()`)
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// // Executing Argpars parser and exiting from the app with a return value
+    /// std::process::exit(args.pars());
+    /// ```
+    fn pars(&self) -> i32 {
+        if self.trace || std::env::var("ARGPARS_DEBUG").is_ok_and(|v| v == "1") {
+            self.display_trace();
+        }
+        if let Some((shell, line, point)) = self.completion_request() {
+            let candidates: Vec<(String, String)> = completions::candidates(self, line, point);
+            self.write(
+                style::Stream::Stdout,
+                &completions::format_candidates(shell, &candidates),
+            );
+            return 0;
+        }
+        // Required arguments are validated even if no arguments were passed
+        self.display_deprecation_warnings();
+        if let Err(err) = self.try_pars() {
+            self.display_error_message(&err);
+            return self.exit_codes.code_for(&err);
+        }
+        if self.no_arguments_passed() {
+            // // Displaying help screen if no arguments were passed and nothing is required (disabled by default):
+            // self.display_help_screen();
+            return 0;
+        }
+        if self.default_argument("--help") && self.passed("--help") {
+            match self.help_topic_index() {
+                Some(index) => self.display_help_topic(&self.arguments_expanded[index]),
+                None => self.display_help_screen(),
+            }
+        }
+        if self.default_argument("--version") && self.passed("--version") {
+            self.display_version();
+        }
+        if !(self.default_arguments && self.default_arguments_passed()) {
+            for (arg, handler) in &self.handlers {
+                if self.passed(arg) {
+                    let return_value: i32 = handler(&self.get_parameters_for(arg));
+                    if return_value != 0 {
+                        return return_value;
+                    }
+                }
+            }
+            if !self.extensions.is_empty() {
+                let matches: Matches = self.matches();
+                for extension in &self.extensions {
+                    extension.handle(&matches);
+                }
+            }
+        }
+        0
+    }
+
+    /// Argpars parser which doesn't print anything, returning found arguments or the first error
+    ///
+    /// Unlike `pars()`, it does not display the help screen or version information
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument("--output", "file to write to");
+    /// match args.try_pars() {
+    ///     Ok(parsed) => println!("output: {}", parsed.get_parameter_for("--output")),
+    ///     Err(err) => eprintln!("ERROR: {}", err),
+    /// }
+    /// ```
+    ///
+    /// A value that is neither a parameter nor bound to a positional argument is reported as unexpected
+    ///
+    /// ```
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::from_vec(split("app --force out.txt"));
+    /// args.add_flag("--force", "overwrite existing files");
+    /// assert!(matches!(args.try_pars(), Err(ArgparsError::UnexpectedValue(value)) if value == "out.txt"));
+    /// ```
+    fn try_pars(&self) -> Result<ParsedArgs, ArgparsError> {
+        if let Some((option, candidates)) = self.ambiguous_abbreviation() {
+            return Err(ArgparsError::AmbiguousOption(
+                option.to_string(),
+                candidates,
+            ));
+        }
+        if let Some(unexpected) = self.first_unexpected_token() {
+            if !self.is_option_token(unexpected) {
+                return Err(ArgparsError::UnexpectedValue(self.shown_token(unexpected)));
+            }
+            return Err(ArgparsError::UnknownOption(self.shown_token(unexpected)));
+        }
+        // Required arguments are not enforced when only asking for help or version
+        if !(self.default_arguments && self.default_arguments_passed()) {
+            if let Some(arg) = self.missing_value() {
+                return Err(ArgparsError::MissingValue(arg.to_string()));
+            }
+            if let Some(missing) = self.missing_required_argument() {
+                return Err(ArgparsError::MissingRequiredArgument(missing.to_string()));
+            }
+            if let Some(missing) = self.missing_required_positional() {
+                return Err(ArgparsError::MissingRequiredPositional(missing.to_string()));
+            }
+            if let Some(count) = self.wrong_number_of_positionals() {
+                return Err(ArgparsError::WrongNumberOfPositionals(
+                    count,
+                    self.messages.range_description(&self.positionals_range),
+                ));
+            }
+            if let Some((arg, value)) = self.invalid_value() {
+                return Err(ArgparsError::InvalidValue(
+                    arg.to_string(),
+                    self.shown_value(arg, value).to_string(),
+                ));
+            }
+            if let Some((arg, value)) = self.invalid_choice() {
+                return Err(ArgparsError::InvalidChoice(
+                    arg.name.clone(),
+                    self.shown_value(&arg.name, value).to_string(),
+                    arg.choices.clone(),
+                ));
+            }
+            if let Some((arg, value, range)) = self.out_of_range() {
+                return Err(ArgparsError::OutOfRange(
+                    arg.to_string(),
+                    self.shown_value(arg, value).to_string(),
+                    self.messages.range_description(range),
+                ));
+            }
+            if let Some((first, second)) = self.conflicting_arguments() {
+                return Err(ArgparsError::ConflictingOptions(
+                    first.to_string(),
+                    second.to_string(),
+                ));
+            }
+            if let Some((arg, required)) = self.missing_dependency() {
+                return Err(ArgparsError::MissingDependency(
+                    arg.to_string(),
+                    required.to_string(),
+                ));
+            }
+        }
+
+        Ok(self.matches())
+    }
+
+    /// Argpars parser returning found arguments in a `Matches` object
+    ///
+    /// Like `pars()`, it displays the help screen, version information or errors,
+    /// exiting from the app when it can't continue
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument_with_short("-v", "--verbose", "explain what is being done");
+    /// args.add_argument("--output", "file to write to");
+    /// args.add_positional("INPUT", "file to read from");
+    /// let matches: Matches = args.parse();
+    /// if matches.is_present("-v") {
+    ///     println!("input: {:?}", matches.positional("INPUT"));
+    ///     println!("output: {:?}", matches.value_of("--output"));
+    /// }
+    /// ```
+    fn parse(&self) -> Matches {
+        let return_value: i32 = self.pars();
+        if return_value != 0 || (self.default_arguments && self.default_arguments_passed()) {
+            std::process::exit(return_value);
+        }
+        // pars() already reported any error
+        self.try_pars().unwrap_or_default()
+    }
+
+    /// Function used for a first, partial pass over passed arguments, returning only the given arguments
+    ///
+    /// Unknown tokens are ignored and nothing is validated, displayed or exited on, so early arguments
+    /// (like `--config`) can be read before the rest of the arguments are registered and `pars()` runs
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_arg(Arg::new("--config", "file to read settings from").takes_value(true));
+    /// let early: Matches = args.partial_pars(&["--config"]);
+    /// if let Some(config) = early.value_of("--config") {
+    ///     println!("loading arguments from {}", config);
+    /// }
+    /// args.add_argument("--verbose", "explain what is being done");
+    /// args.pars();
+    /// ```
+    fn partial_pars(&self, arguments: &[&str]) -> Matches {
+        let selected: Vec<&str> = arguments
+            .iter()
+            .map(|a| self.canonical_argument(a))
+            .collect();
+        let mut parsed: ParsedArgs = self.matches();
+        parsed.arguments.retain(|a| selected.contains(&a.as_str()));
+        parsed
+            .parameters
+            .retain(|a, _| selected.contains(&a.as_str()));
+        parsed.indices.retain(|a, _| selected.contains(&a.as_str()));
+        parsed.positionals.clear();
+        parsed.unknown_arguments.clear();
+        parsed
+    }
+
+    /// Argpars parser which runs `try_pars()` against given arguments instead of the passed ones
+    ///
+    /// The ArgsObj is left unchanged (the program name, the first passed argument, is kept), so one
+    /// configured ArgsObj can parse many command lines, e.g. in interactive applications
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use argpars::*;
+    ///
+    /// let mut args: ArgsObj = Argpars::new();
+    /// args.add_argument_with_short("-v", "--verbose", "explain what is being done");
+    /// args.add_positional("FILE", "file to open");
+    /// for line in ["open.txt -v", "notes.txt"] {
+    ///     match args.parse_from(&split(line).iter().map(|a| a.as_str()).collect::<Vec<&str>>()) {
+    ///         Ok(matches) => println!("{:?} {}", matches.positional("FILE"), matches.is_present("-v")),
+    ///         Err(err) => eprintln!("ERROR: {}", err),
+    ///     }
+    /// }
+    /// ```
+    fn parse_from(&self, arguments: &[&str]) -> Result<Matches, ArgparsError> {
+        let mut args: ArgsObj = self.clone();
+        args.replace_arguments(arguments);
+        args.try_pars()
+    }
+}
+
+/// Internal helpers of the ArgsObj struct
+#[cfg(feature = "std")]
+impl ArgsObj {
+    // Replaces passed arguments (keeping the program name) and refreshes lookups
+    pub(crate) fn replace_arguments(&mut self, arguments: &[&str]) {
+        let program_name: String = self.arguments_passed.first().cloned().unwrap_or_default();
+        let arguments: Vec<String> = std::iter::once(program_name)
+            .chain(arguments.iter().map(|a| a.to_string()))
+            .collect();
+        self.arguments_passed_os = arguments.iter().map(OsString::from).collect();
+        self.number_of_arguments = arguments.len() as u32;
+        self.arguments_passed = arguments;
+        self.refresh_lookups();
+    }
+
+    // Returns arguments, parameters and positional arguments found in passed arguments, without validating them
+    fn matches(&self) -> ParsedArgs {
+        let mut parsed: ParsedArgs = ParsedArgs {
+            trailing_arguments: self.trailing_arguments.clone(),
+            unknown_arguments: self.unknown_args().iter().map(|a| a.to_string()).collect(),
+            raw_arguments: self.arguments_passed.clone(),
+            ..Default::default()
+        };
+        for arg in &self.args {
+            for alias in &arg.aliases {
+                parsed.aliases.insert(alias.clone(), arg.name.clone());
+            }
+            if arg.secret {
+                parsed.secrets.push(arg.name.clone());
+            }
+            if self.passed(&arg.name) {
+                parsed.arguments.push(arg.name.clone());
+                parsed
+                    .indices
+                    .insert(arg.name.clone(), self.occurrences_of(&arg.name));
+            }
+            let parameters: Vec<String> = self
+                .get_parameters_for(&arg.name)
+                .iter()
+                .map(|p| p.to_string())
+                .collect();
+            if !parameters.is_empty() {
+                parsed.parameters.insert(arg.name.clone(), parameters);
+            }
+        }
+        for (positional, index) in self.bound_positionals() {
+            parsed
+                .positionals
+                .insert(positional.name.clone(), self.value_at(index).to_string());
+            parsed.indices.insert(positional.name.clone(), vec![index]);
+        }
+        parsed
+    }
+
+    // Returns the registered argument name or alias closest to an unknown option, if it is close enough
+    fn suggestion_for(&self, option: &str) -> Option<&str> {
+        let option: &str = option.split('=').next().unwrap_or(option);
+        self.args
+            .iter()
+            .filter(|arg| !arg.hidden)
+            .flat_map(|arg| arg.aliases.iter().chain(std::iter::once(&arg.name)))
+            .map(|name| (parser::edit_distance(option, name), name.as_str()))
+            .filter(|(distance, _)| *distance <= (option.chars().count() / 3).max(2))
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, name)| name)
+    }
+
+    // Returns the first two passed arguments of the same exclusive group, if any
+    fn conflicting_arguments(&self) -> Option<(&str, &str)> {
+        for group in &self.exclusive_groups {
+            let passed: Vec<&String> = group.iter().filter(|arg| self.passed(arg)).collect();
+            if passed.len() > 1 {
+                return Some((passed[0], passed[1]));
+            }
+        }
+        None
+    }
+
+    // Returns descriptions of inconsistencies in the definition of the app (see `debug_assert_valid()`)
+    fn definition_problems(&self) -> Vec<String> {
+        let mut problems: Vec<String> = Vec::new();
+        let mut names: Vec<&str> = Vec::new();
+        for name in self
+            .args
+            .iter()
+            .flat_map(|arg| std::iter::once(&arg.name).chain(arg.aliases.iter()))
+        {
+            if names.contains(&name.as_str()) {
+                problems.push(format!("argument '{}' is registered more than once", name));
+            }
+            names.push(name);
+        }
+        let relations = self
+            .requirements
+            .iter()
+            .flat_map(|(arg, required)| [arg, required])
+            .chain(
+                self.conditional_requirements
+                    .iter()
+                    .flat_map(|(arg, condition)| [arg, condition]),
+            )
+            .chain(self.exclusive_groups.iter().flatten())
+            .chain(self.validators.iter().map(|(arg, _)| arg))
+            .chain(self.range_constraints.iter().map(|(arg, _, _)| arg))
+            .chain(
+                self.deprecated_aliases
+                    .iter()
+                    .map(|(_, replacement)| replacement),
+            );
+        for arg in relations {
+            if !self.args.iter().any(|a| a.matches(arg)) {
+                problems.push(format!("argument '{}' is used but not registered", arg));
+            }
+        }
+        for arg in &self.args {
+            if let Some(default) = &arg.default {
+                if !arg.choices.is_empty() && !arg.choices.contains(default) {
+                    problems.push(format!(
+                        "default value '{}' of '{}' is not one of its possible values",
+                        default, arg.name
+                    ));
+                }
+            }
+        }
+        for (i, positional) in self.positionals.iter().enumerate() {
+            if self.positionals[..i]
+                .iter()
+                .any(|p| p.name == positional.name)
+            {
+                problems.push(format!(
+                    "positional argument '{}' is registered more than once",
+                    positional.name
+                ));
+            }
+            if positional.trailing && self.positionals[..i].iter().any(|p| p.trailing) {
+                problems.push(format!(
+                    "trailing positional argument '{}' follows another trailing one",
+                    positional.name
+                ));
+            }
+            if positional.required
+                && !positional.trailing
+                && self.positionals[..i]
+                    .iter()
+                    .any(|p| !p.required && !p.trailing)
+            {
+                problems.push(format!(
+                    "required positional argument '{}' follows an optional one",
+                    positional.name
+                ));
+            }
+        }
+        problems
+    }
+
+    // Returns the first passed argument and the argument it requires which was not passed, if any
+    fn missing_dependency(&self) -> Option<(&str, &str)> {
+        self.requirements
+            .iter()
+            .find(|(arg, required)| self.passed(arg) && !self.passed(required))
+            .map(|(arg, required)| (arg.as_str(), required.as_str()))
+    }
+
+    // Returns the first argument and its parameter rejected by a validator (or missing `=` for a key-value argument),
+    // if any
+    fn invalid_value(&self) -> Option<(&str, &str)> {
+        for arg in self.args.iter().filter(|arg| arg.key_value) {
+            if let Some(value) = self
+                .get_parameters_for(&arg.name)
+                .into_iter()
+                .find(|value| !value.contains('='))
+            {
+                return Some((&arg.name, value));
+            }
+        }
+        for (arg, validator) in &self.validators {
+            if let Some(value) = self
+                .get_parameters_for(arg)
+                .into_iter()
+                .find(|value| !validator(value))
+            {
+                return Some((arg, value));
+            }
+        }
+        None
+    }
+
+    // Returns parameters rejected by a validator, possible values, range or `KEY=VALUE` format of their argument
+    fn rejected_parameters(&self) -> Vec<&str> {
+        let mut rejected: Vec<&str> = Vec::new();
+        for arg in &self.args {
+            rejected.extend(
+                self.get_parameters_for(&arg.name)
+                    .into_iter()
+                    .filter(|value| {
+                        (arg.key_value && !value.contains('='))
+                            || (!arg.choices.is_empty()
+                                && !is_value_in_a_vector_str(value, &arg.choices))
+                    }),
+            );
+        }
+        for (arg, validator) in &self.validators {
+            rejected.extend(
+                self.get_parameters_for(arg)
+                    .into_iter()
+                    .filter(|value| !validator(value)),
+            );
+        }
+        for (arg, _, in_range) in &self.range_constraints {
+            rejected.extend(
+                self.get_parameters_for(arg)
+                    .into_iter()
+                    .filter(|value| !in_range(value)),
+            );
+        }
+        rejected
+    }
+
+    // Returns the first argument with a parameter outside of its allowed values and the parameter, if any
+    fn invalid_choice(&self) -> Option<(&Arg, &str)> {
+        for arg in self.args.iter().filter(|arg| !arg.choices.is_empty()) {
+            if let Some(value) = self
+                .get_parameters_for(&arg.name)
+                .into_iter()
+                .find(|value| !is_value_in_a_vector_str(value, &arg.choices))
+            {
+                return Some((arg, value));
+            }
+        }
+        None
+    }
+
+    // Returns the first argument with a parameter outside of its accepted range, the parameter
+    // and the bounds of the range, if any
+    fn out_of_range(&self) -> Option<(&str, &str, &RangeBoundaries)> {
+        for (arg, range, in_range) in &self.range_constraints {
+            if let Some(value) = self
+                .get_parameters_for(arg)
+                .into_iter()
+                .find(|value| !in_range(value))
+            {
+                return Some((arg, value, range));
+            }
+        }
+        None
+    }
+
+    // Returns the file name of the executable, as passed in the first argument
+    fn program_name(&self) -> String {
+        let path: &str = self.arguments_passed.first().map_or("", |p| p.as_str());
+        std::path::Path::new(path)
+            .file_name()
+            .map_or(path.to_string(), |name| name.to_string_lossy().to_string())
+    }
+
+    // Returns the registered argument with a given name or alias, if any
+    fn find_argument(&self, token: &str) -> Option<&Arg> {
+        if let Some(arg) = self
+            .argument_names
+            .get(token)
+            .and_then(|i| self.args.get(*i))
+        {
+            return Some(arg);
+        }
+        let token: &str = self
+            .deprecated_aliases
+            .iter()
+            .find(|(alias, _)| self.same_long_name(alias, token))
+            .map_or(token, |(_, replacement)| replacement);
+        match self.exact_argument(token) {
+            Some(arg) => Some(arg),
+            None => match self.abbreviated_arguments(token).as_slice() {
+                [arg] => Some(arg),
+                _ => (1..token.len())
+                    .filter(|end| token.is_char_boundary(*end))
+                    .filter_map(|end| self.exact_argument(&token[..end]))
+                    .find(|arg| arg.prefix),
+            },
+        }
+    }
+
+    // Returns the registered argument with a given name or alias, not counting abbreviations
+    fn exact_argument(&self, token: &str) -> Option<&Arg> {
+        if let Some(arg) = self
+            .argument_names
+            .get(token)
+            .and_then(|i| self.args.get(*i))
+        {
+            return Some(arg);
+        }
+        if !self.ignore_case {
+            return None;
+        }
+        self.args.iter().find(|arg| {
+            std::iter::once(&arg.name)
+                .chain(arg.aliases.iter())
+                .any(|name| self.same_long_name(name, token))
+        })
+    }
+
+    // Returns true if a name and a token are the same, ignoring case of long names if enabled
+    fn same_long_name(&self, name: &str, token: &str) -> bool {
+        name == token
+            || (self.ignore_case && name.starts_with("--") && name.eq_ignore_ascii_case(token))
+    }
+
+    // Returns arguments with a long name starting with a token, if abbreviations are allowed
+    fn abbreviated_arguments(&self, token: &str) -> Vec<&Arg> {
+        if !self.allow_abbreviations || !token.starts_with("--") || token.len() <= 2 {
+            return Vec::new();
+        }
+        self.args
+            .iter()
+            .filter(|arg| {
+                std::iter::once(&arg.name)
+                    .chain(arg.aliases.iter())
+                    .any(|name| {
+                        name.starts_with("--")
+                            && name
+                                .get(..token.len())
+                                .is_some_and(|start| self.same_long_name(start, token))
+                    })
+            })
+            .collect()
+    }
+
+    // Returns the first passed abbreviation matching more than one argument and names of these arguments, if any
+    fn ambiguous_abbreviation(&self) -> Option<(&str, Vec<String>)> {
+        self.arguments_expanded.iter().skip(1).find_map(|token| {
+            let candidates: Vec<&Arg> = self.abbreviated_arguments(token);
+            if candidates.len() > 1 && self.exact_argument(token).is_none() {
+                Some((
+                    token.as_str(),
+                    candidates.iter().map(|arg| arg.name.clone()).collect(),
+                ))
+            } else {
+                None
+            }
+        })
+    }
+
+    // Returns the content of a help topic, or of the topic of an argument given by its alias
+    fn help_topic_content(&self, topic: &str) -> Option<&str> {
+        let canonical: &str = self.canonical_argument(topic);
+        self.help_topics
+            .iter()
+            .find(|(name, _)| name == topic || self.canonical_argument(name) == canonical)
+            .map(|(_, content)| content.as_str())
+    }
+
+    // Returns the index of the token following `--help`, if it is a help topic or an argument
+    fn help_topic_index(&self) -> Option<usize> {
+        if !self.default_argument("--help") {
+            return None;
+        }
+        let index: usize = self
+            .arguments_expanded
+            .iter()
+            .skip(1)
+            .position(|a| a == "--help")?
+            + 2;
+        let token: &str = self.arguments_expanded.get(index)?;
+        if self.help_topic_content(token).is_some() || self.help_topic_argument(token).is_some() {
+            Some(index)
+        } else {
+            None
+        }
+    }
+
+    // Returns the argument described by a help topic, unless it's hidden
+    fn help_topic_argument(&self, topic: &str) -> Option<&Arg> {
+        self.find_argument(topic).filter(|arg| !arg.hidden)
+    }
+
+    // Returns the help screen wrapped to a given width
+    fn help_text(&self, width: usize, colored: bool) -> String {
+        if let Some(template) = &self.help_template {
+            let rendered: String = template
+                .replace("{name}", &self.help_name)
+                .replace("{version}", &self.help_version)
+                .replace("{description}", &self.help_description)
+                .replace("{usage}", self.usage().trim_end())
+                .replace("{options}", self.help_options(width, colored).trim_end())
+                .replace(
+                    "{positionals}",
+                    self.help_positionals(width, colored).trim_end(),
+                )
+                .replace("{sections}", self.help_sections_text(colored).trim_end())
+                .replace("{before_help}", self.before_help.trim_end())
+                .replace("{after_help}", self.after_help.trim_end());
+            return format!("{}\n", rendered);
+        }
+        let mut help: String = format!("{}\n", self.usage());
+        help.push_str(&format!("{} {}\n", self.messages.name, self.help_name));
+        help.push_str(&format!(
+            "{} {}\n",
+            self.messages.description, self.help_description
+        ));
+        help.push_str(&format!(
+            "{} {}\n\n",
+            self.messages.version, self.help_version
+        ));
+        if !self.before_help.is_empty() {
+            help.push_str(&format!("{}\n\n", self.before_help.trim_end()));
+        }
+        help.push_str(&format!(
+            "{}\n",
+            style::paint(&self.messages.options, style::HEADER, colored)
+        ));
+        help.push_str(&self.help_options(width, colored));
+        if !self.positionals.is_empty() {
+            help.push_str(&format!(
+                "\n{}\n",
+                style::paint(&self.messages.positionals, style::HEADER, colored)
+            ));
+            help.push_str(&self.help_positionals(width, colored));
+        }
+        if !self.help_sections.is_empty() {
+            help.push('\n');
+            help.push_str(&self.help_sections_text(colored));
+        }
+        if !self.after_help.is_empty() {
+            help.push_str(&format!("\n{}\n", self.after_help.trim_end()));
+        }
+        help
+    }
+
+    // Returns names of an argument as shown in the help screen, without and with colors
+    fn help_names(arg: &Arg, colored: bool) -> (String, String) {
+        let names: Vec<&String> = arg
+            .aliases
+            .iter()
+            .chain(std::iter::once(&arg.name))
+            .collect();
+        let plain: Vec<&str> = names.iter().map(|name| name.as_str()).collect();
+        let painted: Vec<String> = names
+            .iter()
+            .map(|name| style::paint(name, style::OPTION, colored))
+            .collect();
+        if arg.prefix {
+            let value: &str = "<key>=<value>";
+            let plain: Vec<String> = plain
+                .iter()
+                .map(|name| format!("{}{}", name, value))
+                .collect();
+            let painted: Vec<String> = painted
+                .iter()
+                .map(|name| format!("{}{}", name, value))
+                .collect();
+            return (plain.join(", "), painted.join(", "));
+        }
+        match arg.placeholder() {
+            Some(value) => {
+                let value: String = format!(" <{}>", value);
+                (plain.join(", ") + &value, painted.join(", ") + &value)
+            }
+            None => (plain.join(", "), painted.join(", ")),
+        }
+    }
+
+    // Returns the width of the first column of the help screen (the widest argument name)
+    fn help_column_width(&self) -> usize {
+        let options = self
+            .args
+            .iter()
+            .filter(|arg| !arg.hidden)
+            .map(|arg| layout::display_width(&Self::help_names(arg, false).0));
+        let positionals = self
+            .positionals
+            .iter()
+            .map(|p| layout::display_width(&p.name));
+        options.chain(positionals).max().unwrap_or(0)
+    }
+
+    // Returns a help screen row with the description aligned into the second column
+    // and wrapped to a given width
+    fn help_row(&self, plain: &str, painted: &str, description: &str, width: usize) -> String {
+        let column: usize = self.help_column_width();
+        let indent: usize = 2 + column + 2;
+        let mut row: String = format!("  {}", painted);
+        let lines: Vec<String> = layout::wrap(description, width.saturating_sub(indent).max(20));
+        for (i, line) in lines.iter().enumerate() {
+            if i == 0 {
+                row.push_str(
+                    &" ".repeat((indent - 2).saturating_sub(layout::display_width(plain))),
+                );
+            } else {
+                row.push('\n');
+                row.push_str(&" ".repeat(indent));
+            }
+            row.push_str(line);
+        }
+        row.push('\n');
+        row
+    }
+
+    // Returns lines of the help screen describing arguments which aren't hidden, followed by groups
+    // of arguments under their headings
+    fn help_options(&self, width: usize, colored: bool) -> String {
+        let mut options: String = self.help_group_options(None, width, colored);
+        let mut groups: Vec<&str> = Vec::new();
+        for arg in self.help_ordered_args() {
+            if let Some(group) = &arg.group {
+                if !groups.contains(&group.as_str()) {
+                    groups.push(group);
+                }
+            }
+        }
+        for group in groups {
+            let heading: String = if group.ends_with(':') {
+                group.to_string()
+            } else {
+                format!("{}:", group)
+            };
+            options.push_str(&format!(
+                "\n{}\n",
+                style::paint(&heading, style::HEADER, colored)
+            ));
+            options.push_str(&self.help_group_options(Some(group), width, colored));
+        }
+        options
+    }
+
+    // Returns arguments which aren't hidden in the order of the help screen: by display order keys,
+    // then by help_order, with default arguments last if requested
+    fn help_ordered_args(&self) -> Vec<&Arg> {
+        let mut args: Vec<&Arg> = self.args.iter().filter(|arg| !arg.hidden).collect();
+        if self.help_order == HelpOrder::Alphabetical {
+            args.sort_by_cached_key(|arg| arg.name.trim_start_matches('-').to_lowercase());
+        }
+        args.sort_by_key(|arg| arg.display_order.unwrap_or(usize::MAX));
+        if self.default_arguments_last {
+            args.sort_by_key(|arg| arg.name == "--help" || arg.name == "--version");
+        }
+        args
+    }
+
+    // Returns a description followed by markers (possible values, default, ...) for the help screen
+    fn help_description(description: &str, markers: &[String]) -> String {
+        std::iter::once(description.trim())
+            .chain(markers.iter().map(|marker| marker.as_str()))
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ")
+    }
+
+    // Returns lines of the help screen describing arguments of a group which aren't hidden
+    fn help_group_options(&self, group: Option<&str>, width: usize, colored: bool) -> String {
+        let mut options: String = String::new();
+        for arg in self
+            .help_ordered_args()
+            .into_iter()
+            .filter(|arg| arg.group.as_deref() == group)
+        {
+            let (plain, painted) = Self::help_names(arg, colored);
+            let mut markers: Vec<String> = Vec::new();
+            if !arg.choices.is_empty() {
+                markers.push(format!(
+                    "[{} {}]",
+                    self.messages.possible_values,
+                    arg.choices.join(", ")
+                ));
+            }
+            if let Some(default) = &arg.default {
+                markers.push(format!(
+                    "[{} {}]",
+                    self.messages.default,
+                    self.shown_value(&arg.name, default)
+                ));
+            }
+            if let Some(variable) = &arg.env {
+                markers.push(format!("[{} {}]", self.messages.env, variable));
+            }
+            if arg.required {
+                markers.push(format!("({})", self.messages.required));
+            }
+            let description: String = Self::help_description(&arg.description, &markers);
+            options.push_str(&self.help_row(&plain, &painted, &description, width));
+        }
+        options
+    }
+
+    // Returns lines of the help screen describing positional arguments
+    fn help_positionals(&self, width: usize, colored: bool) -> String {
+        let mut positionals: String = String::new();
+        for positional in &self.positionals {
+            let mut markers: Vec<String> = Vec::new();
+            if positional.required {
+                markers.push(format!("({})", self.messages.required));
+            }
+            positionals.push_str(&self.help_row(
+                &positional.name,
+                &style::paint(&positional.name, style::OPTION, colored),
+                &Self::help_description(&positional.description, &markers),
+                width,
+            ));
+        }
+        positionals
+    }
+
+    // Returns help sections with their content
+    fn help_sections_text(&self, colored: bool) -> String {
+        let mut sections: String = String::new();
+        for section in &self.help_sections {
+            sections.push_str(&style::paint(section, style::HEADER, colored));
+            sections.push('\n');
+            if let Some(index) = self.help_sections_content.iter().position(|a| a == section) {
+                if let Some(content) = self.help_sections_content.get(index + 1) {
+                    sections.push_str(content);
+                    sections.push('\n');
+                }
+            }
+        }
+        sections
+    }
+
+    // Writes text into the writer set for a given stream, or into stdout or stderr
+    fn write(&self, stream: style::Stream, text: &str) {
+        let writer: Option<&Rc<RefCell<dyn Write>>> = match stream {
+            style::Stream::Stdout => self.output.as_ref(),
+            style::Stream::Stderr => self.error_output.as_ref(),
+        };
+        match (writer, stream) {
+            (Some(writer), _) => {
+                let _ = writer.borrow_mut().write_all(text.as_bytes());
+            }
+            (None, style::Stream::Stdout) => print!("{}", text),
+            (None, style::Stream::Stderr) => eprint!("{}", text),
+        }
+    }
+
+    // Writes the help screen into stdout, through a pager if it doesn't fit on the terminal
+    fn write_help(&self, help: &str) {
+        let paged: bool = self.paginate_help
+            && self.output.is_none()
+            && std::io::stdout().is_terminal()
+            && layout::terminal_height().is_some_and(|height| help.lines().count() >= height)
+            && pager::page(help);
+        if !paged {
+            self.write(style::Stream::Stdout, help);
+        }
+    }
+
+    // Returns true if colors should be used for a given stream (with `ColorChoice::Auto`,
+    // never when it was redirected into a writer)
+    fn colors_enabled(&self, stream: style::Stream) -> bool {
+        let redirected: bool = match stream {
+            style::Stream::Stdout => self.output.is_some(),
+            style::Stream::Stderr => self.error_output.is_some(),
+        };
+        if redirected {
+            self.color == ColorChoice::Always
+        } else {
+            style::enabled(self.color, stream)
+        }
+    }
+
+    // Prints a warning for every deprecated alias that was passed
+    fn display_deprecation_warnings(&self) {
+        let colored: bool = self.colors_enabled(style::Stream::Stderr);
+        for (alias, replacement) in &self.deprecated_aliases {
+            if self.arguments_expanded.iter().skip(1).any(|a| a == alias) {
+                self.write(
+                    style::Stream::Stderr,
+                    &format!(
+                        "{} {}\n",
+                        style::paint(&self.messages.warning, style::WARNING, colored),
+                        self.messages.deprecated_alias(alias, replacement)
+                    ),
+                );
+            }
+        }
+    }
+
+    // Returns the name of an argument if its alias was given, otherwise the token itself
+    fn canonical_argument<'a>(&'a self, token: &'a str) -> &'a str {
+        match self.find_argument(token) {
+            Some(arg) => &arg.name,
+            None => token,
+        }
+    }
+
+    // Returns positions of every occurrence of an argument in arguments_expanded, from the index built by refresh_lookups()
+    fn positions_of(&self, arg: &str) -> &[usize] {
+        self.occurrences
+            .get(self.canonical_argument(arg))
+            .map_or(&[], |positions| positions.as_slice())
+    }
+
+    // Returns true if an argument never takes a parameter: if it was declared as a flag, or in strict mode
+    // if it wasn't declared as taking a parameter (except for --help, which can be followed by a help topic)
+    fn is_flag(&self, arg: &Arg) -> bool {
+        arg.is_flag()
+            || (self.strict_values
+                && !arg.takes_value
+                && !(arg.name == "--help" && self.default_argument("--help")))
+    }
+
+    // Returns positions of occurrences of an argument which can be followed by its parameter (none for flags)
+    fn parameter_positions_of(&self, arg: &str) -> &[usize] {
+        if self.find_argument(arg).is_some_and(|a| self.is_flag(a)) {
+            return &[];
+        }
+        self.positions_of(arg)
+    }
+
+    // Adds an argument (and its `--no-` form) without updating lookups of passed arguments
+    fn insert_arg(&mut self, mut arg: Arg) {
+        if let Some(duplicate) = std::iter::once(&arg.name)
+            .chain(arg.aliases.iter())
+            .find(|name| *name != "-V" && self.argument_names.contains_key(*name))
+        {
+            panic!(
+                "argpars: argument '{}' is registered more than once (while adding '{}')",
+                duplicate, arg.name
+            );
+        }
+        if arg.group.is_none() {
+            arg.group = self.current_group.clone();
+        }
+        // A user-defined argument takes over the -V short form of --version
+        if arg.matches("-V") {
+            for existing in &mut self.args {
+                if existing.name == "--version" {
+                    existing.aliases.retain(|alias| alias != "-V");
+                }
+            }
+        }
+        let negation: Option<Arg> = match arg.name.strip_prefix("--") {
+            Some(name) if arg.negatable => {
+                let mut negation: Arg = Arg::new(
+                    &format!("--no-{}", name),
+                    &self.messages.negation_description(&arg.name),
+                );
+                negation.group = arg.group.clone();
+                negation.hidden = arg.hidden;
+                Some(negation)
+            }
+            _ => None,
+        };
+        self.passed_arguments_lookup.insert(arg.name.clone(), false);
+        self.parameters_lookup
+            .insert(arg.name.clone(), "".to_string());
+        for name in std::iter::once(&arg.name).chain(arg.aliases.iter()) {
+            self.argument_names.insert(name.clone(), self.args.len());
+        }
+        self.args.push(arg);
+        if let Some(negation) = negation {
+            self.insert_arg(negation);
+        }
+    }
+
+    // Refreshes lookups of passed arguments (see `lookup_update()`), keeping the index of argument names
+    fn refresh_lookups(&mut self) {
+        let separator: usize = self
+            .arguments_passed
+            .iter()
+            .skip(1)
+            .position(|a| a == "--")
+            .map_or(self.arguments_passed.len(), |i| i + 1);
+        self.trailing_arguments = self
+            .arguments_passed
+            .iter()
+            .skip(separator + 1)
+            .cloned()
+            .collect();
+        self.options_end = separator;
+        if self.posix_mode {
+            // Looking for the first passed value which is not a parameter of the preceding argument,
+            // expanding passed arguments one by one
+            let mut expanded: Vec<String> = Vec::new();
+            let mut owner: usize = 0;
+            for (i, token) in self.arguments_passed[..separator].iter().enumerate() {
+                let start: usize = expanded.len();
+                self.expand_token(i, token, &mut expanded);
+                if i == 0 || expanded.len() == start {
+                    continue;
+                }
+                for (j, token) in expanded.iter().enumerate().skip(start) {
+                    if self.is_option_token(token) {
+                        owner = j;
+                    }
+                }
+                let last: usize = expanded.len() - 1;
+                if !self.is_option_token(&expanded[last])
+                    && self
+                        .find_argument(&expanded[owner])
+                        .filter(|arg| !self.is_flag(arg) && (owner == last - 1 || arg.multi_value))
+                        .is_none()
+                {
+                    self.options_end = i;
+                    break;
+                }
+            }
+        }
+        if self.options_end < separator {
+            let mut operands: Vec<String> =
+                self.arguments_passed[self.options_end..separator].to_vec();
+            if separator < self.arguments_passed.len() {
+                operands.push("--".to_string());
+            }
+            operands.append(&mut self.trailing_arguments);
+            self.trailing_arguments = operands;
+        }
+        self.arguments_expanded = self.expand_arguments(&self.arguments_passed[..self.options_end]);
+        let mut occurrences: HashMap<String, Vec<usize>> = HashMap::new();
+        let mut preceding_options: Vec<usize> = Vec::with_capacity(self.arguments_expanded.len());
+        let mut compound_tokens: Vec<usize> = Vec::new();
+        let mut last_option: usize = 0;
+        for (i, token) in self.arguments_expanded.iter().enumerate() {
+            preceding_options.push(last_option);
+            if i == 0 {
+                continue;
+            }
+            if self.is_option_token(token) {
+                last_option = i;
+            }
+            let canonical: &str = self.canonical_argument(token);
+            if canonical == token
+                && !self.is_known_argument(token)
+                && token.starts_with('-')
+                && (token.contains('=') || (!token.starts_with("--") && token.len() > 2))
+            {
+                compound_tokens.push(i);
+            }
+            occurrences
+                .entry(canonical.to_string())
+                .or_default()
+                .push(i);
+        }
+        self.occurrences = occurrences;
+        self.preceding_options = preceding_options;
+        self.compound_tokens = compound_tokens;
+        self.environment_lookup.clear();
+        for index in 0..self.args.len() {
+            self.lookup_argument(index);
+        }
+    }
+
+    // Rebuilds the index of names and aliases of arguments
+    fn index_argument_names(&mut self) {
+        self.argument_names.clear();
+        for (i, arg) in self.args.iter().enumerate() {
+            for name in std::iter::once(&arg.name).chain(arg.aliases.iter()) {
+                self.argument_names.entry(name.clone()).or_insert(i);
+            }
+        }
+    }
+
+    // Refreshes the environment variable, passed state and parameters of an argument in lookups
+    fn lookup_argument(&mut self, index: usize) {
+        let arg: &Arg = &self.args[index];
+        let name: String = arg.name.clone();
+        if let Some(value) = arg.env.as_ref().and_then(|v| std::env::var(v).ok()) {
+            self.environment_lookup.insert(name.clone(), value);
+        }
+        let passed: bool = self.passed(&name);
+        let (parameter, parameters): (String, Vec<String>) = if passed {
+            (
+                self.get_parameter_for(&name).to_string(),
+                self.get_parameters_for(&name)
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect(),
+            )
+        } else {
+            (String::new(), Vec::new())
+        };
+        self.passed_arguments_lookup.insert(name.clone(), passed);
+        self.parameters_lookup.insert(name.clone(), parameter);
+        self.parameters_list_lookup.insert(name, parameters);
+    }
+
+    // Updates lookups after arguments were added from a given position of args. Only passed unknown options
+    // equal to their names (or aliases) are moved over to them, unless passed arguments could refer to them
+    // in other ways (abbreviations, bundles, `--key=value` pairs, ...), in which case refresh_lookups() is called
+    fn lookup_insert(&mut self, start: usize) {
+        let names: Vec<String> = self.args[start..]
+            .iter()
+            .flat_map(|arg| std::iter::once(&arg.name).chain(arg.aliases.iter()))
+            .cloned()
+            .collect();
+        let exact_only: bool = !self.allow_abbreviations
+            && !self.ignore_case
+            && !self.windows_options
+            && !self.posix_mode
+            && !self.args.iter().any(|arg| arg.prefix)
+            && !names.iter().any(|name| name == "-V")
+            && !self.deprecated_aliases.iter().any(|(alias, replacement)| {
+                names.contains(alias)
+                    || (names.contains(replacement) && self.occurrences.contains_key(alias))
+            })
+            && names.iter().all(|name| {
+                self.occurrences.get(name).is_none_or(|positions| {
+                    // The option already ended parameters of preceding multi-value arguments,
+                    // but not of preceding single-value ones
+                    name.len() > 1
+                        && name.starts_with('-')
+                        && !parser::is_negative_number(name)
+                        && positions.iter().all(|position| {
+                            !self.is_known_argument(&self.arguments_expanded[position - 1])
+                        })
+                })
+            })
+            && !self.compound_tokens.iter().any(|&i| {
+                let token: &str = &self.arguments_expanded[i];
+                names.iter().any(|name| {
+                    token
+                        .strip_prefix(name.as_str())
+                        .is_some_and(|rest| rest.starts_with('='))
+                        || (name.len() == 2
+                            && name.starts_with('-')
+                            && !name.starts_with("--")
+                            && token[1..].contains(&name[1..]))
+                })
+            });
+        if !exact_only {
+            self.refresh_lookups();
+            return;
+        }
+        for index in start..self.args.len() {
+            let arg: &Arg = &self.args[index];
+            let mut positions: Vec<usize> = std::iter::once(&arg.name)
+                .chain(arg.aliases.iter())
+                .flat_map(|name| self.occurrences.remove(name).unwrap_or_default())
+                .collect();
+            if !positions.is_empty() {
+                positions.sort_unstable();
+                self.occurrences.insert(arg.name.clone(), positions);
+            }
+            self.lookup_argument(index);
+        }
+    }
+
+    // Returns a parameter of an argument as it can be shown, `***` if the argument is secret
+    fn shown_value<'a>(&self, arg: &str, value: &'a str) -> &'a str {
+        if self.find_argument(arg).is_some_and(|a| a.secret) {
+            REDACTED
+        } else {
+            value
+        }
+    }
+
+    // Returns arguments_expanded with parameters of secret arguments replaced with `***`
+    fn redacted_expanded(&self) -> Vec<String> {
+        self.arguments_expanded
+            .iter()
+            .enumerate()
+            .map(|(i, token)| {
+                let secret: bool = i > 0
+                    && !self.is_option_token(token)
+                    && self.parameter_owner(i).is_some_and(|arg| arg.secret);
+                if secret {
+                    REDACTED.to_string()
+                } else {
+                    self.shown_token(token)
+                }
+            })
+            .collect()
+    }
+
+    // Returns a token with the parameter of a secret argument passed as `--password=x` replaced with `***`,
+    // even if the argument doesn't take one
+    fn shown_token(&self, token: &str) -> String {
+        match token.split_once('=') {
+            Some((key, _))
+                if key.starts_with('-') && self.find_argument(key).is_some_and(|a| a.secret) =>
+            {
+                format!("{}={}", key, REDACTED)
+            }
+            _ => token.to_string(),
+        }
+    }
+
+    // Returns arguments_passed with parameters of secret arguments (`--password x` or `--password=x`)
+    // replaced with `***`
+    fn redacted_passed(&self) -> Vec<String> {
+        let mut redacted: Vec<String> = Vec::new();
+        let mut secret_next: bool = false;
+        for (i, token) in self.arguments_passed.iter().enumerate() {
+            if i == 0 || i >= self.options_end {
+                redacted.push(token.clone());
+                continue;
+            }
+            if std::mem::take(&mut secret_next) && !self.is_option_token(token) {
+                redacted.push(REDACTED.to_string());
+                continue;
+            }
+            let shown: String = self.shown_token(token);
+            if shown != *token {
+                redacted.push(shown);
+                continue;
+            }
+            secret_next = self
+                .find_argument(token)
+                .is_some_and(|a| a.secret && !self.is_flag(a));
+            redacted.push(token.clone());
+        }
+        redacted
+    }
+
+    // Returns parameters of arguments with ones of secret arguments replaced with `***`
+    fn redacted_values<'a>(
+        &self,
+        values: &'a HashMap<String, String>,
+    ) -> HashMap<&'a str, &'a str> {
+        values
+            .iter()
+            .map(|(arg, value)| (arg.as_str(), self.shown_value(arg, value)))
+            .collect()
+    }
+
+    // Returns true if the number of positional arguments was restricted with positionals_range()
+    fn positionals_range_set(&self) -> bool {
+        self.positionals_range != (Bound::Unbounded, Bound::Unbounded)
+    }
+
+    // Returns the number of passed positional arguments if it is outside of the accepted range
+    fn wrong_number_of_positionals(&self) -> Option<usize> {
+        let count: usize = self.positional_args().len();
+        if self.positionals_range.contains(&count) {
+            None
+        } else {
+            Some(count)
+        }
+    }
+
+    // Returns true if a token is a registered argument or an alias of one
+    fn is_known_argument(&self, token: &str) -> bool {
+        self.find_argument(token).is_some()
+    }
+
+    // Returns the parameter of an argument used when it wasn't passed: from the environment,
+    // the config file or the default
+    fn fallback_value(&self, canonical: &str) -> Option<&str> {
+        self.fallback_source(canonical).map(|(value, _)| value)
+    }
+
+    // Returns the fallback value of an argument with its source
+    fn fallback_source(&self, canonical: &str) -> Option<(&str, ValueSource)> {
+        if let Some(value) = self.environment_lookup.get(canonical) {
+            return Some((value, ValueSource::Environment));
+        }
+        if let Some(value) = self.config_values.get(canonical) {
+            return Some((value, ValueSource::ConfigFile));
+        }
+        self.find_argument(canonical)
+            .and_then(|a| a.default.as_deref())
+            .map(|value| (value, ValueSource::Default))
+    }
+
+    // Returns the parameter passed to the first occurrence of an argument, if any
+    fn passed_parameter(&self, canonical: &str) -> Option<&str> {
+        let index_of_argument: usize = *self.parameter_positions_of(canonical).first()?;
+        let index_of_parameter: usize = index_of_argument + 1;
+        if index_of_parameter < self.parameters_end()
+            && !self.is_known_argument(&self.arguments_expanded[index_of_parameter])
+        {
+            return Some(&self.arguments_expanded[index_of_parameter]);
+        }
+        None
+    }
+
+    // Returns the first passed argument declared as taking a parameter which is not followed by one
+    // (at the end of passed arguments or before another argument), if any. Arguments with a default
+    // (or another fallback value) fall back to it instead
+    fn missing_value(&self) -> Option<&str> {
+        self.args
+            .iter()
+            .filter(|arg| arg.takes_value && !arg.prefix)
+            .filter(|arg| self.fallback_value(&arg.name).is_none())
+            .find(|arg| {
+                self.positions_of(&arg.name).iter().any(|index| {
+                    self.arguments_expanded[..self.parameters_end()]
+                        .get(index + 1)
+                        .is_none_or(|next| self.is_option_token(next))
+                })
+            })
+            .map(|arg| arg.name.as_str())
+    }
+
+    // Returns the first required argument (or one required because of another passed argument)
+    // which was not passed, if any
+    fn missing_required_argument(&self) -> Option<&str> {
+        self.args
+            .iter()
+            .find(|arg| {
+                (arg.required
+                    || self
+                        .conditional_requirements
+                        .iter()
+                        .any(|(required, condition)| {
+                            *required == arg.name && self.passed(condition)
+                        }))
+                    && !self.passed(&arg.name)
+                    && !self.environment_lookup.contains_key(&arg.name)
+                    && !self.config_values.contains_key(&arg.name)
+            })
+            .map(|arg| arg.name.as_str())
+    }
+
+    // Returns the first required positional argument which was not passed, if any
+    fn missing_required_positional(&self) -> Option<&str> {
+        let bound: Vec<(&Positional, usize)> = self.bound_positionals();
+        self.positionals
+            .iter()
+            .find(|p| p.required && !bound.iter().any(|(b, _)| b.name == p.name))
+            .map(|p| p.name.as_str())
+    }
+
+    // Returns true if a token at a given index of arguments_expanded is not an argument or a parameter of one
+    fn is_free_token(&self, index: usize) -> bool {
+        if index == 0
+            || self.is_option_token(&self.arguments_expanded[index])
+            || self.trailing_positional_index() == Some(index)
+        {
+            return false;
+        }
+        self.parameter_owner(index).is_none()
+    }
+
+    // Returns the shell, the command line and the cursor position passed with `--argpars-complete`,
+    // if dynamic completion is enabled (the cursor is at the end of the line if not given)
+    fn completion_request(&self) -> Option<(Shell, &str, usize)> {
+        if !self.dynamic_completions || self.arguments_passed.get(1)? != "--argpars-complete" {
+            return None;
+        }
+        let shell: Shell = Shell::from_name(self.arguments_passed.get(2)?)?;
+        let line: &str = self.arguments_passed.get(3).map_or("", |l| l.as_str());
+        let point: usize = self
+            .arguments_passed
+            .get(4)
+            .and_then(|p| p.parse().ok())
+            .unwrap_or(line.chars().count());
+        Some((shell, line, point))
+    }
+
+    // Returns true if a given default argument (`--help` or `--version`) is enabled
+    fn default_argument(&self, name: &str) -> bool {
+        self.default_arguments && !self.disabled_default_arguments.iter().any(|a| a == name)
+    }
+
+    // Returns the argument a value at a given index of arguments_expanded is a parameter of, if any
+    fn parameter_owner(&self, index: usize) -> Option<&Arg> {
+        if self.trailing_positional_index() == Some(index) {
+            return None;
+        }
+        self.preceding_owner(index)
+    }
+
+    // Returns the argument preceding a value at a given index of arguments_expanded which takes it
+    // as a parameter, not taking a trailing positional argument into account
+    fn preceding_owner(&self, index: usize) -> Option<&Arg> {
+        // Looking for the closest preceding argument, as all values after a multi-value argument are its parameters
+        let owner_index: usize = self.preceding_options.get(index).copied().unwrap_or(0);
+        self.find_argument(&self.arguments_expanded[owner_index])
+            .filter(|arg| !self.is_flag(arg) && (owner_index == index - 1 || arg.multi_value))
+    }
+
+    // Prints how each passed token was classified, mirroring unexpected_tokens()
+    fn display_trace(&self) {
+        let mut trace: String = String::new();
+        let mut positionals = self.positionals.iter().filter(|p| !p.trailing);
+        let trailing: Option<(&Positional, usize)> = self.bound_trailing_positional();
+        let tokens: Vec<String> = self.redacted_expanded();
+        for (i, token) in self.arguments_expanded.iter().enumerate().skip(1) {
+            #[allow(deprecated)]
+            let classification: String =
+                if let Some((positional, _)) = trailing.filter(|(_, index)| *index == i) {
+                    format!("positional '{}'", positional.name)
+                } else if self.last_param_ok && i == self.arguments_expanded.len() - 1 {
+                    "last parameter".to_string()
+                } else if self.is_option_token(token) {
+                    match self.find_argument(token) {
+                        Some(arg) => format!("option '{}'", arg.name),
+                        None => "unknown option".to_string(),
+                    }
+                } else if let Some(owner) = self.parameter_owner(i) {
+                    format!("parameter of '{}'", owner.name)
+                } else if let Some(positional) = positionals.next() {
+                    format!("positional '{}'", positional.name)
+                } else {
+                    "unexpected value".to_string()
+                };
+            trace.push_str(&format!("argpars: '{}' -> {}\n", tokens[i], classification));
+        }
+        for (i, token) in self.trailing_arguments.iter().enumerate() {
+            let index: usize = self.arguments_expanded.len() + 1 + i;
+            let positional: Option<&Positional> = match trailing {
+                Some((positional, trailing_index)) if trailing_index == index => Some(positional),
+                _ => positionals.next(),
+            };
+            let classification: String = match positional {
+                Some(positional) => format!("positional '{}' (after '--')", positional.name),
+                None => "trailing value (after '--')".to_string(),
+            };
+            trace.push_str(&format!("argpars: '{}' -> {}\n", token, classification));
+        }
+        self.write(style::Stream::Stderr, &trace);
+    }
+
+    // Returns positions of passed values which are not arguments or their parameters, in order
+    // (values passed after the `--` separator are counted after arguments_expanded and the separator)
+    // (the value bound to a trailing positional argument is left out)
+    fn positional_indices(&self) -> Vec<usize> {
+        let trailing_start: usize = self.arguments_expanded.len() + 1;
+        let reserved: Option<usize> = self.trailing_positional_index();
+        (1..self.arguments_expanded.len())
+            .filter(|i| self.is_free_token(*i))
+            .chain(trailing_start..trailing_start + self.trailing_arguments.len())
+            .filter(|i| Some(*i) != reserved)
+            .collect()
+    }
+
+    // Returns the passed value at a position counted like in positional_indices()
+    fn value_at(&self, index: usize) -> &str {
+        match self.arguments_expanded.get(index) {
+            Some(value) => value,
+            None => self
+                .trailing_arguments
+                .get(index.saturating_sub(self.arguments_expanded.len() + 1))
+                .map_or("", |a| a.as_str()),
+        }
+    }
+
+    // Returns the position of the last passed value if a trailing positional argument is registered,
+    // counted like in positional_indices() (a trailing positional argument takes precedence over parameters)
+    fn trailing_positional_index(&self) -> Option<usize> {
+        if !self.positionals.iter().any(|p| p.trailing) {
+            return None;
+        }
+        if !self.trailing_arguments.is_empty() {
+            return Some(self.arguments_expanded.len() + self.trailing_arguments.len());
+        }
+        let last: usize = self.arguments_expanded.len().checked_sub(1)?;
+        if last == 0 || self.is_option_token(&self.arguments_expanded[last]) {
+            return None;
+        }
+        Some(last)
+    }
+
+    // Returns the end of the part of arguments_expanded which can hold parameters of arguments
+    fn parameters_end(&self) -> usize {
+        self.trailing_positional_index()
+            .filter(|index| *index < self.arguments_expanded.len())
+            .unwrap_or(self.arguments_expanded.len())
+    }
+
+    // Returns the trailing positional argument with the position of its value, if it was passed
+    fn bound_trailing_positional(&self) -> Option<(&Positional, usize)> {
+        let positional: &Positional = self.positionals.iter().find(|p| p.trailing)?;
+        Some((positional, self.trailing_positional_index()?))
+    }
+
+    // Returns passed positional arguments with positions of their values, counted like in positional_indices()
+    fn bound_positionals(&self) -> Vec<(&Positional, usize)> {
+        let mut bound: Vec<(&Positional, usize)> = self
+            .positionals
+            .iter()
+            .filter(|p| !p.trailing)
+            .zip(self.positional_indices())
+            .collect();
+        bound.extend(self.bound_trailing_positional());
+        bound
+    }
+
+    // Returns the first passed token which is not a known argument, a parameter of one or a positional argument,
+    // unless unknown arguments are allowed
+    fn first_unexpected_token(&self) -> Option<&str> {
+        if self.allow_unknown {
+            return None;
+        }
+        self.unexpected_tokens().first().copied()
+    }
+
+    // Returns passed tokens which are not known arguments, parameters of ones or positional arguments
+    fn unexpected_tokens(&self) -> Vec<&str> {
+        let mut unexpected: Vec<&str> = Vec::new();
+        let mut loop_end: usize = self.arguments_expanded.len();
+        #[allow(deprecated)]
+        if self.last_param_ok {
+            loop_end = loop_end.saturating_sub(1);
+        }
+        let mut positionals_left: usize = self.positionals.iter().filter(|p| !p.trailing).count();
+        for i in 1..loop_end {
+            let token: &str = &self.arguments_expanded[i];
+            // If there is a '-' character at the beginning and it is not an known argument, it is unexpected
+            if self.is_option_token(token) {
+                if !self.is_known_argument(token) {
+                    unexpected.push(token);
+                }
+            }
+            // If the previous argument is not a known one, the token has to be bound to a positional argument
+            else if self.is_free_token(i) {
+                // With a range of positional arguments, extra values are counted instead
+                if positionals_left == 0 && !self.positionals_range_set() {
+                    unexpected.push(token);
+                } else {
+                    positionals_left = positionals_left.saturating_sub(1);
+                }
+            }
+        }
+        unexpected
+    }
+
+    // Returns true if a token starts with a '-' character and is not a negative number (e.g. `-5` or `-3.14`)
+    // or a lone `-` (stdin), unless it is registered as an argument
+    fn is_option_token(&self, token: &str) -> bool {
+        self.is_known_argument(token)
+            || (token.len() > 1 && token.starts_with('-') && !parser::is_negative_number(token))
+    }
+
+    // Returns true if a token is a bundle of registered short flags (e.g. `-abc`)
+    fn is_short_bundle(&self, token: &str) -> bool {
+        token.starts_with('-')
+            && !token.starts_with("--")
+            && token.chars().count() > 2
+            && !self.is_known_argument(token)
+            && token
+                .chars()
+                .skip(1)
+                .all(|c| self.is_known_argument(&format!("-{}", c)))
+    }
+
+    // Returns the argument and parameter of a `--key=value` token if the key is a registered argument
+    fn split_key_value<'a>(&self, token: &'a str) -> Option<(&'a str, &'a str)> {
+        if !token.starts_with('-') {
+            return None;
+        }
+        let (key, value) = token.split_once('=')?;
+        if self
+            .find_argument(key)
+            .is_some_and(|arg| !self.is_flag(arg))
+        {
+            Some((key, value))
+        } else {
+            None
+        }
+    }
+
+    // Returns the registered argument name and the parameter (if any) of a Windows-style `/name` or `/name:value`
+    // token, if they are enabled
+    fn windows_option<'a>(&'a self, token: &'a str) -> Option<(&'a str, Option<&'a str>)> {
+        if !self.windows_options {
+            return None;
+        }
+        let option: &str = token.strip_prefix('/')?;
+        if option == "?" && self.default_argument("--help") {
+            return Some(("--help", None));
+        }
+        let (key, value) = match option.split_once(':') {
+            Some((key, value)) => (key, Some(value)),
+            None => (option, None),
+        };
+        if key.is_empty() {
+            return None;
+        }
+        let arg: &Arg = self.args.iter().find(|arg| {
+            std::iter::once(&arg.name)
+                .chain(arg.aliases.iter())
+                .any(|name| {
+                    let name: &str = name.trim_start_matches('-');
+                    name == key || (self.ignore_case && name.eq_ignore_ascii_case(key))
+                })
+        })?;
+        Some((&arg.name, value))
+    }
+
+    // Returns given arguments with Windows-style arguments translated (if enabled), bundled short flags
+    // (`-abc` becomes `-a -b -c`) and `--key=value` pairs (`--key=value` becomes `--key value`) split up
+    fn expand_arguments(&self, arguments: &[String]) -> Vec<String> {
+        let mut expanded: Vec<String> = Vec::new();
+        for (i, token) in arguments.iter().enumerate() {
+            self.expand_token(i, token, &mut expanded);
+        }
+        expanded
+    }
+
+    // Appends a passed argument at a given position to expanded arguments (see expand_arguments())
+    fn expand_token(&self, i: usize, token: &str, expanded: &mut Vec<String>) {
+        if i == 0 || self.find_argument(token).is_some_and(|arg| arg.prefix) {
+            expanded.push(token.to_string());
+        } else if i == 1 && token == "help" && self.help_command && self.default_argument("--help")
+        {
+            expanded.push("--help".to_string());
+        } else if let Some((name, value)) = self.windows_option(token) {
+            expanded.push(name.to_string());
+            expanded.extend(value.map(|v| v.to_string()));
+        } else if let Some((key, value)) = self.split_key_value(token) {
+            expanded.push(key.to_string());
+            expanded.push(value.to_string());
+        } else if self.is_short_bundle(token) {
+            for c in token.chars().skip(1) {
+                expanded.push(format!("-{}", c));
+            }
+        } else {
+            expanded.push(token.to_string());
+        }
     }
 }
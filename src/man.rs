@@ -0,0 +1,105 @@
+//! Man page generation
+
+use crate::{Argpars, ArgsObj, REDACTED};
+
+// Returns text escaped for use in roff
+fn escape(text: &str) -> String {
+    let mut escaped: String = String::new();
+    for line in text.trim().lines() {
+        let line: String = line.trim().replace('\\', "\\e").replace('-', "\\-");
+        if line.starts_with('.') || line.starts_with('\'') {
+            escaped.push_str("\\&");
+        }
+        escaped.push_str(&line);
+        escaped.push('\n');
+    }
+    escaped
+}
+
+// Returns the man page of the app in roff format
+pub(crate) fn generate(args: &ArgsObj, program: &str) -> String {
+    let mut page: String = String::new();
+    page.push_str(&format!(
+        ".TH \"{}\" 1 \"\" \"{} {}\"\n",
+        program.to_uppercase(),
+        args.help_name.replace('"', ""),
+        args.help_version.replace('"', "")
+    ));
+
+    page.push_str(".SH NAME\n");
+    page.push_str(&escape(&format!("{} - {}", program, args.help_description)));
+
+    page.push_str(".SH SYNOPSIS\n");
+    let usage: String = args.usage();
+    let usage: &str = usage.trim();
+    let usage: &str = usage.strip_prefix("Usage:").unwrap_or(usage);
+    match args
+        .arguments_passed
+        .first()
+        .filter(|path| !path.is_empty())
+    {
+        Some(path) => page.push_str(&escape(&usage.replace(path.as_str(), program))),
+        None => page.push_str(&escape(usage)),
+    }
+
+    page.push_str(".SH DESCRIPTION\n");
+    page.push_str(&escape(&args.help_description));
+
+    if args.args.iter().any(|arg| !arg.hidden) {
+        page.push_str(".SH OPTIONS\n");
+        for arg in args.args.iter().filter(|arg| !arg.hidden) {
+            let names: Vec<String> = arg
+                .aliases
+                .iter()
+                .chain(std::iter::once(&arg.name))
+                .map(|name| format!("\\fB{}\\fR", escape(name).trim_end()))
+                .collect();
+            page.push_str(".TP\n");
+            page.push_str(&names.join(", "));
+            if let Some(value) = arg.placeholder() {
+                page.push_str(&format!(" \\fI{}\\fR", escape(&value).trim_end()));
+            }
+            page.push('\n');
+            page.push_str(&escape(&arg.description));
+            if !arg.choices.is_empty() {
+                page.push_str(&escape(&format!(
+                    "[possible values: {}]",
+                    arg.choices.join(", ")
+                )));
+            }
+            if let Some(default) = &arg.default {
+                // The default of a secret argument is not exported
+                let default: &str = if arg.secret { REDACTED } else { default };
+                page.push_str(&escape(&format!("[default: {}]", default)));
+            }
+        }
+    }
+
+    if !args.positionals.is_empty() {
+        page.push_str(".SH ARGUMENTS\n");
+        for positional in &args.positionals {
+            page.push_str(".TP\n");
+            page.push_str(&format!(
+                "\\fI{}\\fR\n",
+                escape(&positional.name).trim_end()
+            ));
+            page.push_str(&escape(&positional.description));
+        }
+    }
+
+    for section in &args.help_sections {
+        page.push_str(&format!(
+            ".SH {}\n",
+            escape(section.trim_end_matches(':'))
+                .trim_end()
+                .to_uppercase()
+        ));
+        if let Some(index) = args.help_sections_content.iter().position(|a| a == section) {
+            if let Some(content) = args.help_sections_content.get(index + 1) {
+                page.push_str(&escape(content));
+            }
+        }
+    }
+
+    page
+}
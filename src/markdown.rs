@@ -0,0 +1,135 @@
+//! Markdown documentation generation
+
+use crate::{Arg, Argpars, ArgsObj, REDACTED};
+
+// Returns text escaped for use in a Markdown table cell
+fn cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<&str>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+// Returns a table of arguments
+fn options_table(args: &[&Arg]) -> String {
+    let mut table: String =
+        String::from("| Option | Description | Default |\n| --- | --- | --- |\n");
+    for arg in args {
+        let names: Vec<String> = arg
+            .aliases
+            .iter()
+            .chain(std::iter::once(&arg.name))
+            .map(|name| match arg.placeholder() {
+                Some(value) => format!("`{} <{}>`", name, value),
+                None => format!("`{}`", name),
+            })
+            .collect();
+        let mut description: String = cell(&arg.description);
+        if arg.required {
+            description.push_str(" (required)");
+        }
+        if !arg.choices.is_empty() {
+            description.push_str(&format!(
+                " (possible values: {})",
+                arg.choices
+                    .iter()
+                    .map(|c| format!("`{}`", cell(c)))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+        let default: String = arg
+            .default
+            .as_ref()
+            // The default of a secret argument is not exported
+            .map(|d| if arg.secret { REDACTED } else { d })
+            .map_or(String::new(), |d| format!("`{}`", cell(d)));
+        table.push_str(&format!(
+            "| {} | {} | {} |\n",
+            names.join(", "),
+            description.trim(),
+            default
+        ));
+    }
+    table
+}
+
+// Returns the reference of the app (usage, options, positional arguments and help sections) in Markdown
+pub(crate) fn generate(args: &ArgsObj, program: &str) -> String {
+    let mut document: String = format!("# {}\n\n", args.help_name);
+    if !args.help_description.is_empty() {
+        document.push_str(&format!("{}\n\n", args.help_description.trim()));
+    }
+    if !args.help_version.is_empty() {
+        document.push_str(&format!("Version: {}\n\n", args.help_version));
+    }
+
+    let usage: String = args.usage();
+    let usage: &str = usage.trim();
+    document.push_str("## Usage\n\n```\n");
+    match args
+        .arguments_passed
+        .first()
+        .filter(|path| !path.is_empty())
+    {
+        Some(path) => document.push_str(&usage.replace(path.as_str(), program)),
+        None => document.push_str(usage),
+    }
+    document.push_str("\n```\n");
+
+    let visible: Vec<&Arg> = args.args.iter().filter(|arg| !arg.hidden).collect();
+    let ungrouped: Vec<&Arg> = visible
+        .iter()
+        .copied()
+        .filter(|arg| arg.group.is_none())
+        .collect();
+    if !visible.is_empty() {
+        document.push_str("\n## Options\n\n");
+        if !ungrouped.is_empty() {
+            document.push_str(&options_table(&ungrouped));
+        }
+        let mut groups: Vec<&str> = Vec::new();
+        for arg in &visible {
+            if let Some(group) = &arg.group {
+                if !groups.contains(&group.as_str()) {
+                    groups.push(group);
+                }
+            }
+        }
+        for group in groups {
+            let grouped: Vec<&Arg> = visible
+                .iter()
+                .copied()
+                .filter(|arg| arg.group.as_deref() == Some(group))
+                .collect();
+            document.push_str(&format!("\n### {}\n\n", group.trim_end_matches(':')));
+            document.push_str(&options_table(&grouped));
+        }
+    }
+
+    if !args.positionals.is_empty() {
+        document.push_str("\n## Arguments\n\n| Argument | Description |\n| --- | --- |\n");
+        for positional in &args.positionals {
+            let mut description: String = cell(&positional.description);
+            if positional.required {
+                description.push_str(" (required)");
+            }
+            document.push_str(&format!(
+                "| `{}` | {} |\n",
+                positional.name,
+                description.trim()
+            ));
+        }
+    }
+
+    for section in &args.help_sections {
+        document.push_str(&format!("\n## {}\n", section.trim_end_matches(':')));
+        if let Some(index) = args.help_sections_content.iter().position(|a| a == section) {
+            if let Some(content) = args.help_sections_content.get(index + 1) {
+                document.push_str(&format!("\n```\n{}\n```\n", content.trim_end()));
+            }
+        }
+    }
+
+    document
+}
@@ -0,0 +1,383 @@
+//! Message catalog of built-in strings (error messages, help screen headings, ...)
+
+use crate::ArgparsError;
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+
+/// Built-in strings of argpars, which can be replaced to translate the app (see `Argpars::set_messages()`)
+///
+/// Messages can contain placeholders in braces (e.g. `{arg}`), replaced with actual values when displayed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Messages {
+    /// Label of error messages
+    pub error: String,
+    /// Label of warnings
+    pub warning: String,
+    /// `{option}`
+    pub unknown_option: String,
+    /// `{option}`, `{candidates}`
+    pub ambiguous_option: String,
+    /// `{arg}`
+    pub missing_value: String,
+    /// `{arg}`
+    pub missing_required_argument: String,
+    /// `{arg}`
+    pub missing_required_positional: String,
+    /// `{arg}`, `{value}`
+    pub invalid_value: String,
+    /// `{arg}`, `{value}`, `{choices}`
+    pub invalid_choice: String,
+    /// `{arg}`, `{value}`, `{range}`
+    pub out_of_range: String,
+    /// `{arg}`, `{other}`
+    pub conflicting_options: String,
+    /// `{arg}`, `{other}`
+    pub missing_dependency: String,
+    /// `{count}`, `{range}`
+    pub wrong_number_of_positionals: String,
+    /// `{value}`
+    pub unexpected_value: String,
+    /// `{suggestion}`
+    pub did_you_mean: String,
+    /// `{program}`
+    pub try_help: String,
+    /// `{alias}`, `{replacement}`
+    pub deprecated_alias: String,
+    /// Label of the usage line
+    pub usage: String,
+    /// Label of the app name in the help screen
+    pub name: String,
+    /// Label of the app description in the help screen
+    pub description: String,
+    /// Label of the app version in the help screen
+    pub version: String,
+    /// Heading of arguments in the help screen
+    pub options: String,
+    /// Heading of positional arguments in the help screen
+    pub positionals: String,
+    /// Label of possible values of an argument in the help screen
+    pub possible_values: String,
+    /// Label of the default value of an argument in the help screen
+    pub default: String,
+    /// Label of the environment variable of an argument in the help screen
+    pub env: String,
+    /// Marker of required arguments and positional arguments in the help screen
+    pub required: String,
+    /// Description of `--help`
+    pub help_description: String,
+    /// Description of `--version`
+    pub version_description: String,
+    /// Description of the `--no-` form of a negatable argument: `{arg}`
+    pub negation_description: String,
+    /// Inclusive lower bound of an accepted range: `{value}`
+    pub range_at_least: String,
+    /// Exclusive lower bound of an accepted range: `{value}`
+    pub range_greater_than: String,
+    /// Inclusive upper bound of an accepted range: `{value}`
+    pub range_at_most: String,
+    /// Exclusive upper bound of an accepted range: `{value}`
+    pub range_less_than: String,
+    /// Accepted range with both bounds inclusive: `{start}`, `{end}`
+    pub range_inclusive: String,
+    /// Accepted range with both bounds, joining their descriptions: `{start}`, `{end}`
+    pub range_bounds: String,
+}
+
+impl Default for Messages {
+    fn default() -> Messages {
+        Messages::english()
+    }
+}
+
+// Returns a message with placeholders replaced with values
+fn fill(message: &str, values: &[(&str, &str)]) -> String {
+    let mut filled: String = message.to_string();
+    for (placeholder, value) in values {
+        filled = filled.replace(&format!("{{{}}}", placeholder), value);
+    }
+    filled
+}
+
+impl Messages {
+    /// English messages (the default)
+    pub fn english() -> Messages {
+        Messages {
+            error: "ERROR:".to_string(),
+            warning: "WARNING:".to_string(),
+            unknown_option: "No such option: '{option}'".to_string(),
+            ambiguous_option: "Ambiguous option: '{option}' (could be {candidates})".to_string(),
+            missing_value: "Missing value for argument: '{arg}'".to_string(),
+            missing_required_argument: "Missing required argument: '{arg}'".to_string(),
+            missing_required_positional: "Missing required positional argument: '{arg}'"
+                .to_string(),
+            invalid_value: "Invalid value '{value}' for argument: '{arg}'".to_string(),
+            invalid_choice:
+                "Invalid value '{value}' for argument: '{arg}' (possible values: {choices})"
+                    .to_string(),
+            out_of_range:
+                "Value '{value}' for argument: '{arg}' is out of range (accepted: {range})"
+                    .to_string(),
+            conflicting_options: "Option '{arg}' cannot be used together with '{other}'"
+                .to_string(),
+            missing_dependency: "Option '{arg}' requires '{other}' to be passed as well"
+                .to_string(),
+            wrong_number_of_positionals:
+                "Wrong number of positional arguments: {count} (accepted: {range})".to_string(),
+            unexpected_value: "Unexpected positional value: '{value}'".to_string(),
+            did_you_mean: "Did you mean '{suggestion}'?".to_string(),
+            try_help: "Try: '{program} --help' for more information.".to_string(),
+            deprecated_alias: "'{alias}' is deprecated, use '{replacement}' instead".to_string(),
+            usage: "Usage:".to_string(),
+            name: "Name:".to_string(),
+            description: "Description:".to_string(),
+            version: "Version:".to_string(),
+            options: "Possible options:".to_string(),
+            positionals: "Positional arguments:".to_string(),
+            possible_values: "possible values:".to_string(),
+            default: "default:".to_string(),
+            env: "env:".to_string(),
+            required: "required".to_string(),
+            help_description: "display this help and exit".to_string(),
+            version_description: "output version information and exit".to_string(),
+            negation_description: "disable {arg}".to_string(),
+            range_at_least: "at least {value}".to_string(),
+            range_greater_than: "greater than {value}".to_string(),
+            range_at_most: "at most {value}".to_string(),
+            range_less_than: "less than {value}".to_string(),
+            range_inclusive: "{start} to {end}".to_string(),
+            range_bounds: "{start} and {end}".to_string(),
+        }
+    }
+
+    /// German messages
+    pub fn german() -> Messages {
+        Messages {
+            error: "FEHLER:".to_string(),
+            warning: "WARNUNG:".to_string(),
+            unknown_option: "Unbekannte Option: '{option}'".to_string(),
+            ambiguous_option: "Mehrdeutige Option: '{option}' (möglich: {candidates})".to_string(),
+            missing_value: "Fehlender Wert für Argument: '{arg}'".to_string(),
+            missing_required_argument: "Fehlendes erforderliches Argument: '{arg}'".to_string(),
+            missing_required_positional: "Fehlendes erforderliches Positionsargument: '{arg}'"
+                .to_string(),
+            invalid_value: "Ungültiger Wert '{value}' für Argument: '{arg}'".to_string(),
+            invalid_choice:
+                "Ungültiger Wert '{value}' für Argument: '{arg}' (mögliche Werte: {choices})"
+                    .to_string(),
+            out_of_range:
+                "Wert '{value}' für Argument: '{arg}' liegt außerhalb des Bereichs (erlaubt: {range})"
+                    .to_string(),
+            conflicting_options:
+                "Option '{arg}' kann nicht zusammen mit '{other}' verwendet werden".to_string(),
+            missing_dependency: "Option '{arg}' erfordert zusätzlich '{other}'".to_string(),
+            wrong_number_of_positionals:
+                "Falsche Anzahl von Positionsargumenten: {count} (erlaubt: {range})".to_string(),
+            unexpected_value: "Unerwarteter Positionswert: '{value}'".to_string(),
+            did_you_mean: "Meinten Sie '{suggestion}'?".to_string(),
+            try_help: "Versuchen Sie '{program} --help' für weitere Informationen.".to_string(),
+            deprecated_alias: "'{alias}' ist veraltet, verwenden Sie stattdessen '{replacement}'"
+                .to_string(),
+            usage: "Aufruf:".to_string(),
+            name: "Name:".to_string(),
+            description: "Beschreibung:".to_string(),
+            version: "Version:".to_string(),
+            options: "Mögliche Optionen:".to_string(),
+            positionals: "Positionsargumente:".to_string(),
+            possible_values: "mögliche Werte:".to_string(),
+            default: "Standard:".to_string(),
+            env: "Umgebungsvariable:".to_string(),
+            required: "erforderlich".to_string(),
+            help_description: "diese Hilfe anzeigen und beenden".to_string(),
+            version_description: "Versionsinformationen ausgeben und beenden".to_string(),
+            negation_description: "{arg} deaktivieren".to_string(),
+            range_at_least: "mindestens {value}".to_string(),
+            range_greater_than: "größer als {value}".to_string(),
+            range_at_most: "höchstens {value}".to_string(),
+            range_less_than: "kleiner als {value}".to_string(),
+            range_inclusive: "{start} bis {end}".to_string(),
+            range_bounds: "{start} und {end}".to_string(),
+        }
+    }
+
+    /// Spanish messages
+    pub fn spanish() -> Messages {
+        Messages {
+            error: "ERROR:".to_string(),
+            warning: "AVISO:".to_string(),
+            unknown_option: "Opción desconocida: '{option}'".to_string(),
+            ambiguous_option: "Opción ambigua: '{option}' (podría ser {candidates})".to_string(),
+            missing_value: "Falta el valor del argumento: '{arg}'".to_string(),
+            missing_required_argument: "Falta el argumento obligatorio: '{arg}'".to_string(),
+            missing_required_positional: "Falta el argumento posicional obligatorio: '{arg}'"
+                .to_string(),
+            invalid_value: "Valor no válido '{value}' para el argumento: '{arg}'".to_string(),
+            invalid_choice:
+                "Valor no válido '{value}' para el argumento: '{arg}' (valores posibles: {choices})"
+                    .to_string(),
+            out_of_range:
+                "El valor '{value}' del argumento: '{arg}' está fuera de rango (aceptado: {range})"
+                    .to_string(),
+            conflicting_options: "La opción '{arg}' no se puede usar junto con '{other}'"
+                .to_string(),
+            missing_dependency: "La opción '{arg}' requiere que también se pase '{other}'"
+                .to_string(),
+            wrong_number_of_positionals:
+                "Número incorrecto de argumentos posicionales: {count} (aceptado: {range})"
+                    .to_string(),
+            unexpected_value: "Valor posicional inesperado: '{value}'".to_string(),
+            did_you_mean: "¿Quiso decir '{suggestion}'?".to_string(),
+            try_help: "Pruebe '{program} --help' para más información.".to_string(),
+            deprecated_alias: "'{alias}' está obsoleto, use '{replacement}' en su lugar"
+                .to_string(),
+            usage: "Uso:".to_string(),
+            name: "Nombre:".to_string(),
+            description: "Descripción:".to_string(),
+            version: "Versión:".to_string(),
+            options: "Opciones posibles:".to_string(),
+            positionals: "Argumentos posicionales:".to_string(),
+            possible_values: "valores posibles:".to_string(),
+            default: "predeterminado:".to_string(),
+            env: "variable de entorno:".to_string(),
+            required: "obligatorio".to_string(),
+            help_description: "muestra esta ayuda y sale".to_string(),
+            version_description: "muestra la información de versión y sale".to_string(),
+            negation_description: "desactiva {arg}".to_string(),
+            range_at_least: "al menos {value}".to_string(),
+            range_greater_than: "mayor que {value}".to_string(),
+            range_at_most: "como máximo {value}".to_string(),
+            range_less_than: "menor que {value}".to_string(),
+            range_inclusive: "{start} a {end}".to_string(),
+            range_bounds: "{start} y {end}".to_string(),
+        }
+    }
+
+    /// Messages in the language of the user, taken from the `LC_ALL`, `LC_MESSAGES` or `LANG`
+    /// environment variables (English if the language has no built-in messages)
+    pub fn from_env() -> Messages {
+        let locale: String = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|variable| std::env::var(variable).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        match locale.get(..2) {
+            Some("de") => Messages::german(),
+            Some("es") => Messages::spanish(),
+            _ => Messages::english(),
+        }
+    }
+
+    /// Function returning the message describing an error
+    pub fn error_message(&self, err: &ArgparsError) -> String {
+        match err {
+            ArgparsError::UnknownOption(option) => {
+                fill(&self.unknown_option, &[("option", option)])
+            }
+            ArgparsError::AmbiguousOption(option, candidates) => {
+                let candidates: Vec<String> =
+                    candidates.iter().map(|c| format!("'{}'", c)).collect();
+                fill(
+                    &self.ambiguous_option,
+                    &[("option", option), ("candidates", &candidates.join(", "))],
+                )
+            }
+            ArgparsError::MissingValue(arg) => fill(&self.missing_value, &[("arg", arg)]),
+            ArgparsError::MissingRequiredArgument(arg) => {
+                fill(&self.missing_required_argument, &[("arg", arg)])
+            }
+            ArgparsError::MissingRequiredPositional(name) => {
+                fill(&self.missing_required_positional, &[("arg", name)])
+            }
+            ArgparsError::InvalidValue(arg, value) => {
+                fill(&self.invalid_value, &[("arg", arg), ("value", value)])
+            }
+            ArgparsError::InvalidChoice(arg, value, choices) => fill(
+                &self.invalid_choice,
+                &[
+                    ("arg", arg),
+                    ("value", value),
+                    ("choices", &choices.join(", ")),
+                ],
+            ),
+            ArgparsError::OutOfRange(arg, value, range) => fill(
+                &self.out_of_range,
+                &[("arg", arg), ("value", value), ("range", range)],
+            ),
+            ArgparsError::ConflictingOptions(first, second) => fill(
+                &self.conflicting_options,
+                &[("arg", first), ("other", second)],
+            ),
+            ArgparsError::MissingDependency(arg, required) => fill(
+                &self.missing_dependency,
+                &[("arg", arg), ("other", required)],
+            ),
+            ArgparsError::WrongNumberOfPositionals(count, range) => fill(
+                &self.wrong_number_of_positionals,
+                &[("count", &count.to_string()), ("range", range)],
+            ),
+            ArgparsError::UnexpectedValue(value) => {
+                fill(&self.unexpected_value, &[("value", value)])
+            }
+        }
+    }
+
+    // Returns the "Did you mean" hint for a suggested argument
+    pub(crate) fn did_you_mean(&self, suggestion: &str) -> String {
+        fill(&self.did_you_mean, &[("suggestion", suggestion)])
+    }
+
+    // Returns the hint pointing to the help screen of a program
+    pub(crate) fn try_help(&self, program: &str) -> String {
+        fill(&self.try_help, &[("program", program)])
+    }
+
+    // Returns the warning about a deprecated alias
+    pub(crate) fn deprecated_alias(&self, alias: &str, replacement: &str) -> String {
+        fill(
+            &self.deprecated_alias,
+            &[("alias", alias), ("replacement", replacement)],
+        )
+    }
+
+    // Returns the description of the `--no-` form of an argument
+    pub(crate) fn negation_description(&self, arg: &str) -> String {
+        fill(&self.negation_description, &[("arg", arg)])
+    }
+
+    // Returns the description of an accepted range, e.g. "1 to 10" or "at least 1"
+    pub(crate) fn range_description<T: fmt::Display, R: RangeBounds<T>>(
+        &self,
+        range: &R,
+    ) -> String {
+        if let (Bound::Included(start), Bound::Included(end)) =
+            (range.start_bound(), range.end_bound())
+        {
+            return fill(
+                &self.range_inclusive,
+                &[("start", &start.to_string()), ("end", &end.to_string())],
+            );
+        }
+        let start: Option<String> = match range.start_bound() {
+            Bound::Included(start) => {
+                Some(fill(&self.range_at_least, &[("value", &start.to_string())]))
+            }
+            Bound::Excluded(start) => Some(fill(
+                &self.range_greater_than,
+                &[("value", &start.to_string())],
+            )),
+            Bound::Unbounded => None,
+        };
+        let end: Option<String> = match range.end_bound() {
+            Bound::Included(end) => Some(fill(&self.range_at_most, &[("value", &end.to_string())])),
+            Bound::Excluded(end) => {
+                Some(fill(&self.range_less_than, &[("value", &end.to_string())]))
+            }
+            Bound::Unbounded => None,
+        };
+        match (start, end) {
+            (Some(start), Some(end)) => {
+                fill(&self.range_bounds, &[("start", &start), ("end", &end)])
+            }
+            (start, end) => start.or(end).unwrap_or_default(),
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! Paging long output through `$PAGER`
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+// Pagers tried when the `PAGER` environment variable is not set
+const DEFAULT_PAGERS: [&str; 2] = ["less", "more"];
+
+// Returns pager commands to try, in order
+fn pagers() -> Vec<Vec<String>> {
+    let mut pagers: Vec<Vec<String>> = Vec::new();
+    if let Ok(pager) = std::env::var("PAGER") {
+        let command: Vec<String> = crate::response_file::split(&pager);
+        if !command.is_empty() {
+            pagers.push(command);
+        }
+    }
+    pagers.extend(DEFAULT_PAGERS.iter().map(|pager| vec![pager.to_string()]));
+    pagers
+}
+
+// Writes text into the first pager that could be started and waits for it to exit,
+// returns false if none could be started
+pub(crate) fn page(text: &str) -> bool {
+    for command in pagers() {
+        let mut pager: Command = Command::new(&command[0]);
+        pager.args(&command[1..]).stdin(Stdio::piped());
+        // Let less pass colors through and exit right away if the text fits on the screen after all
+        if std::env::var_os("LESS").is_none() {
+            pager.env("LESS", "FRX");
+        }
+        let mut child = match pager.spawn() {
+            Ok(child) => child,
+            Err(_) => continue,
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            // The user quitting the pager early closes the pipe, which is fine
+            let _ = stdin.write_all(text.as_bytes());
+        }
+        let _ = child.wait();
+        return true;
+    }
+    false
+}
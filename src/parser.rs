@@ -0,0 +1,272 @@
+//! Minimal argument matching, usable without the standard library (`no_std` + `alloc`)
+//!
+//! It only splits tokens into arguments, their parameters and positional values according to
+//! specifications of arguments. It is a separate, smaller parser: `ArgsObj` (`std` feature) doesn't
+//! use it, so features like abbreviations, prefix arguments, `--flag=value` for flags, Windows-style
+//! options, the help screen, environment variables or config files are not available here
+
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Specification of an argument recognized by `parse()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Spec<'a> {
+    pub name: &'a str,
+    pub aliases: &'a [&'a str],
+    pub takes_value: bool,
+    pub multi_value: bool,
+}
+
+impl<'a> Spec<'a> {
+    /// Spec constructor
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use argpars::parser::Spec;
+    ///
+    /// const OUTPUT: Spec = Spec::new("--output").aliases(&["-o"]).takes_value(true);
+    /// ```
+    pub const fn new(name: &'a str) -> Spec<'a> {
+        Spec {
+            name,
+            aliases: &[],
+            takes_value: false,
+            multi_value: false,
+        }
+    }
+
+    /// Function used to set aliases (e.g. short forms) of the argument
+    pub const fn aliases(mut self, aliases: &'a [&'a str]) -> Spec<'a> {
+        self.aliases = aliases;
+        self
+    }
+
+    /// Function used to mark the argument as expecting a parameter
+    pub const fn takes_value(mut self, takes_value: bool) -> Spec<'a> {
+        self.takes_value = takes_value;
+        self
+    }
+
+    /// Function used to mark the argument as accepting multiple parameters
+    pub const fn multi_value(mut self, multi_value: bool) -> Spec<'a> {
+        self.multi_value = multi_value;
+        if multi_value {
+            self.takes_value = true;
+        }
+        self
+    }
+
+    // Returns true if a token is the name or one of the aliases of the argument
+    fn matches(&self, token: &str) -> bool {
+        self.name == token || self.aliases.contains(&token)
+    }
+}
+
+/// Occurrence of an argument found by `parse()`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match<'a> {
+    /// Name of the argument (even if one of its aliases was passed)
+    pub name: &'a str,
+    /// Position of the argument in the parsed tokens
+    pub index: usize,
+    /// Parameters passed to this occurrence of the argument
+    pub values: Vec<&'a str>,
+}
+
+/// Arguments, parameters and positional values found by `parse()`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Parsed<'a> {
+    /// Occurrences of arguments, in order
+    pub matches: Vec<Match<'a>>,
+    /// Values which are not arguments or their parameters, in order
+    pub positionals: Vec<&'a str>,
+    /// Values passed after the `--` separator
+    pub trailing: Vec<&'a str>,
+}
+
+impl<'a> Parsed<'a> {
+    /// Function which checks if an argument (by its name, not an alias) was passed
+    pub fn passed(&self, name: &str) -> bool {
+        self.matches.iter().any(|m| m.name == name)
+    }
+
+    /// Function returning how many times an argument (by its name, not an alias) was passed
+    pub fn count_of(&self, name: &str) -> usize {
+        self.matches.iter().filter(|m| m.name == name).count()
+    }
+
+    /// Function used to retrive parameters of every occurrence of an argument (by its name, not an alias)
+    pub fn values_of(&self, name: &str) -> Vec<&'a str> {
+        self.matches
+            .iter()
+            .filter(|m| m.name == name)
+            .flat_map(|m| m.values.iter().copied())
+            .collect()
+    }
+}
+
+/// Error returned by `parse()`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<'a> {
+    UnknownOption(&'a str),
+    MissingValue(&'a str),
+}
+
+impl fmt::Display for Error<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::UnknownOption(option) => write!(f, "No such option: '{}'", option),
+            Error::MissingValue(arg) => write!(f, "Missing value for argument: '{}'", arg),
+        }
+    }
+}
+
+/// Function which checks if a token is a negative number (e.g. `-5` or `-3.14`) rather than an option
+pub fn is_negative_number(token: &str) -> bool {
+    match token.strip_prefix('-') {
+        Some(number) => {
+            number.starts_with(|c: char| c.is_ascii_digit() || c == '.')
+                && number.parse::<f64>().is_ok()
+        }
+        None => false,
+    }
+}
+
+/// Function returning the Levenshtein distance between two strings
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current: Vec<usize> = alloc::vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution: usize = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+// Returns true if a token looks like an option rather than a value
+fn is_option(specs: &[Spec], token: &str) -> bool {
+    token.len() > 1
+        && token.starts_with('-')
+        && (!is_negative_number(token) || specs.iter().any(|s| s.matches(token)))
+}
+
+// Returns the argument and the parameter of a `--key=value` token if the key is an argument taking a value
+fn key_value<'s, 'a>(specs: &'s [Spec<'a>], token: &'a str) -> Option<(&'s Spec<'a>, &'a str)> {
+    let (key, value) = token.split_once('=')?;
+    let spec: &Spec = specs.iter().find(|s| s.matches(key) && s.takes_value)?;
+    Some((spec, value))
+}
+
+// Returns the argument with a given short form (e.g. `-v` for `v`)
+fn short_spec<'s, 'a>(specs: &'s [Spec<'a>], c: char) -> Option<&'s Spec<'a>> {
+    let is_short = |name: &&str| {
+        name.strip_prefix('-')
+            .is_some_and(|short| short.chars().eq(core::iter::once(c)))
+    };
+    specs
+        .iter()
+        .find(|s| is_short(&s.name) || s.aliases.iter().any(is_short))
+}
+
+// Returns parameters of an argument starting at a given position (which is moved past them): all values
+// up to the next option for multi-value arguments, otherwise the next token unless it's a known argument
+fn values_for<'a>(
+    specs: &[Spec],
+    spec: &Spec<'a>,
+    tokens: &[&'a str],
+    index: &mut usize,
+) -> Result<Vec<&'a str>, Error<'a>> {
+    if !spec.takes_value {
+        return Ok(Vec::new());
+    }
+    let following = tokens[*index..].iter().take_while(|t| **t != "--");
+    let values: Vec<&'a str> = if spec.multi_value {
+        following
+            .take_while(|t| !is_option(specs, t))
+            .copied()
+            .collect()
+    } else {
+        following
+            .take(1)
+            .filter(|t| !specs.iter().any(|s| s.matches(t)))
+            .copied()
+            .collect()
+    };
+    if values.is_empty() {
+        return Err(Error::MissingValue(spec.name));
+    }
+    *index += values.len();
+    Ok(values)
+}
+
+/// Function used to match tokens (without the program name) against specifications of arguments
+///
+/// `--key=value` pairs and bundled short flags (`-abc`) are split up, and values passed after
+/// the `--` separator are returned separately
+///
+/// # Examples
+///
+/// ```
+/// use argpars::parser::{parse, Spec};
+///
+/// let specs = [
+///     Spec::new("--verbose").aliases(&["-v"]),
+///     Spec::new("--output").aliases(&["-o"]).takes_value(true),
+/// ];
+/// let parsed = parse(&specs, &["-vv", "--output=out.txt", "input.txt"]).unwrap();
+/// assert_eq!(parsed.count_of("--verbose"), 2);
+/// assert_eq!(parsed.values_of("--output"), ["out.txt"]);
+/// assert_eq!(parsed.positionals, ["input.txt"]);
+/// ```
+pub fn parse<'a>(specs: &[Spec<'a>], tokens: &[&'a str]) -> Result<Parsed<'a>, Error<'a>> {
+    let mut parsed: Parsed<'a> = Parsed::default();
+    let mut index: usize = 0;
+    while index < tokens.len() {
+        let token: &'a str = tokens[index];
+        let start: usize = index;
+        index += 1;
+        if token == "--" {
+            parsed.trailing = tokens[index..].to_vec();
+            break;
+        }
+        if !is_option(specs, token) {
+            parsed.positionals.push(token);
+        } else if let Some(spec) = specs.iter().find(|s| s.matches(token)) {
+            let values: Vec<&'a str> = values_for(specs, spec, tokens, &mut index)?;
+            parsed.matches.push(Match {
+                name: spec.name,
+                index: start,
+                values,
+            });
+        } else if let Some((spec, value)) = key_value(specs, token) {
+            parsed.matches.push(Match {
+                name: spec.name,
+                index: start,
+                values: alloc::vec![value],
+            });
+        } else if !token.starts_with("--") {
+            // Bundled short flags, where each flag taking a value gets the following tokens
+            let bundle: Vec<&Spec<'a>> = token
+                .chars()
+                .skip(1)
+                .map(|c| short_spec(specs, c).ok_or(Error::UnknownOption(token)))
+                .collect::<Result<Vec<&Spec<'a>>, Error<'a>>>()?;
+            for spec in bundle {
+                let values: Vec<&'a str> = values_for(specs, spec, tokens, &mut index)?;
+                parsed.matches.push(Match {
+                    name: spec.name,
+                    index: start,
+                    values,
+                });
+            }
+        } else {
+            return Err(Error::UnknownOption(token));
+        }
+    }
+    Ok(parsed)
+}
@@ -0,0 +1,44 @@
+//! Response files (`@file`) parsing
+
+// Returns arguments of a response file, split on whitespace like a shell would
+//
+// Single and double quotes group words containing whitespace, and a backslash escapes
+// the next character (except inside single quotes)
+pub(crate) fn split(content: &str) -> Vec<String> {
+    let mut arguments: Vec<String> = Vec::new();
+    let mut current: String = String::new();
+    let mut in_argument: bool = false;
+    let mut quote: Option<char> = None;
+    let mut chars = content.chars();
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (Some('\''), '\'') | (Some('"'), '"') => quote = None,
+            (Some('\''), _) => current.push(c),
+            (_, '\\') => {
+                if let Some(escaped) = chars.next() {
+                    current.push(escaped);
+                }
+                in_argument = true;
+            }
+            (Some(_), _) => current.push(c),
+            (None, '\'') | (None, '"') => {
+                quote = Some(c);
+                in_argument = true;
+            }
+            (None, c) if c.is_whitespace() => {
+                if in_argument {
+                    arguments.push(std::mem::take(&mut current));
+                    in_argument = false;
+                }
+            }
+            (None, _) => {
+                current.push(c);
+                in_argument = true;
+            }
+        }
+    }
+    if in_argument {
+        arguments.push(current);
+    }
+    arguments
+}
@@ -0,0 +1,52 @@
+//! Colored output using raw ANSI escape codes
+
+use std::io::IsTerminal;
+
+/// When to use colors in the help screen and error messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    /// Use colors when writing to a terminal and the `NO_COLOR` environment variable is not set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Output streams which can be colored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Stream {
+    Stdout,
+    Stderr,
+}
+
+// Styles of the colored elements
+pub(crate) const OPTION: &str = "1;32";
+pub(crate) const HEADER: &str = "1;33";
+pub(crate) const ERROR: &str = "1;31";
+pub(crate) const WARNING: &str = "1;35";
+
+// Returns true if colors should be used for a given stream
+pub(crate) fn enabled(choice: ColorChoice, stream: Stream) -> bool {
+    match choice {
+        ColorChoice::Always => true,
+        ColorChoice::Never => false,
+        ColorChoice::Auto => {
+            if std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+                return false;
+            }
+            match stream {
+                Stream::Stdout => std::io::stdout().is_terminal(),
+                Stream::Stderr => std::io::stderr().is_terminal(),
+            }
+        }
+    }
+}
+
+// Returns text wrapped in a given ANSI style if colors are enabled
+pub(crate) fn paint(text: &str, style: &str, enabled: bool) -> String {
+    if enabled && !text.is_empty() {
+        format!("\x1b[{}m{}\x1b[0m", style, text)
+    } else {
+        text.to_string()
+    }
+}
@@ -0,0 +1,55 @@
+//! Helpers for testing command line interfaces built with argpars
+//!
+//! # Examples
+//!
+//! ```
+//! use argpars::*;
+//! use argpars::testing::{pars_with, ParseOutcome};
+//!
+//! let mut args: ArgsObj = Argpars::from_vec(vec!["app".to_string()]);
+//! args.add_argument("--print-stuff", "display \"stuff\"");
+//!
+//! let outcome: ParseOutcome = pars_with(&mut args, &["--print-stuf"]);
+//! assert_eq!(outcome.exit_code, 1);
+//! assert!(outcome.stderr.contains("Did you mean '--print-stuff'?"));
+//! ```
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{Argpars, ArgsObj};
+
+/// Result of running the parser with `pars_with()`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParseOutcome {
+    /// Value returned by `pars()`
+    pub exit_code: i32,
+    /// Text which would have been printed to stdout (help screen, version information)
+    pub stdout: String,
+    /// Text which would have been printed to stderr (errors, warnings)
+    pub stderr: String,
+}
+
+/// Function used to run `pars()` against given arguments, capturing what it prints
+///
+/// The arguments replace the passed ones (the program name, the first passed argument, is kept),
+/// so the same `ArgsObj` can be used for multiple runs
+pub fn pars_with(args: &mut ArgsObj, arguments: &[&str]) -> ParseOutcome {
+    args.replace_arguments(arguments);
+
+    let stdout: Rc<RefCell<Vec<u8>>> = Rc::new(RefCell::new(Vec::new()));
+    let stderr: Rc<RefCell<Vec<u8>>> = Rc::new(RefCell::new(Vec::new()));
+    let output = args.output.replace(stdout.clone());
+    let error_output = args.error_output.replace(stderr.clone());
+    let exit_code: i32 = args.pars();
+    args.output = output;
+    args.error_output = error_output;
+
+    let stdout: String = String::from_utf8_lossy(&stdout.borrow()).to_string();
+    let stderr: String = String::from_utf8_lossy(&stderr.borrow()).to_string();
+    ParseOutcome {
+        exit_code,
+        stdout,
+        stderr,
+    }
+}